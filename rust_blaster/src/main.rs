@@ -33,6 +33,7 @@ struct GamepadState {
     lb_pressed: bool,
     rb_pressed: bool,
     start_pressed: bool,
+    select_pressed: bool,
     dpad_up: bool,
     dpad_down: bool,
     dpad_left: bool,
@@ -44,10 +45,23 @@ struct GamepadState {
     lb_just_pressed: bool,
     rb_just_pressed: bool,
     start_just_pressed: bool,
+    select_just_pressed: bool,
     dpad_up_just: bool,
     dpad_down_just: bool,
     dpad_left_just: bool,
     dpad_right_just: bool,
+    // Menu navigation intents - DPad presses and left-stick flicks folded
+    // together so menus work identically on a D-pad-only or stick-only pad.
+    // Computed by `update_nav`, not written to directly anywhere else.
+    nav_up_just: bool,
+    nav_down_just: bool,
+    nav_left_just: bool,
+    nav_right_just: bool,
+    // Hysteresis state for the stick-flick edge detection, one step per axis
+    // (-1 / 0 / 1). Not reset by `clear_just_pressed` - it tracks the stick's
+    // resting position across frames, not a one-frame edge.
+    stick_nav_y: i8,
+    stick_nav_x: i8,
 }
 
 impl GamepadState {
@@ -57,10 +71,193 @@ impl GamepadState {
         self.lb_just_pressed = false;
         self.rb_just_pressed = false;
         self.start_just_pressed = false;
+        self.select_just_pressed = false;
         self.dpad_up_just = false;
         self.dpad_down_just = false;
         self.dpad_left_just = false;
         self.dpad_right_just = false;
+        self.nav_up_just = false;
+        self.nav_down_just = false;
+        self.nav_left_just = false;
+        self.nav_right_just = false;
+    }
+
+    /// Folds DPad presses and left-stick flicks into the `nav_*_just`
+    /// fields that drive every non-Playing screen. Call once per frame,
+    /// after the DPad `*_just` fields are updated from gilrs events.
+    fn update_nav(&mut self) {
+        const FLICK_IN: f32 = 0.6;
+        const FLICK_OUT: f32 = 0.3;
+        // Stick Y reads positive-up (as `update_player`'s movement uses it),
+        // so the negative flick is the downward one.
+        let (down, up) = Self::flick_axis(self.left_stick_y, &mut self.stick_nav_y, FLICK_IN, FLICK_OUT);
+        let (left, right) = Self::flick_axis(self.left_stick_x, &mut self.stick_nav_x, FLICK_IN, FLICK_OUT);
+        // OR'd in, so an intent from a frame that ran no tick is kept until
+        // `clear_just_pressed` runs after a tick consumes it.
+        self.nav_up_just |= self.dpad_up_just || up;
+        self.nav_down_just |= self.dpad_down_just || down;
+        self.nav_left_just |= self.dpad_left_just || left;
+        self.nav_right_just |= self.dpad_right_just || right;
+    }
+
+    /// Edge-detects a stick axis crossing `flick_in` as a single "just
+    /// pressed" intent, then requires it to fall back under `flick_out`
+    /// before it can fire again - prevents one long hold from repeating.
+    fn flick_axis(value: f32, state: &mut i8, flick_in: f32, flick_out: f32) -> (bool, bool) {
+        if *state == 0 {
+            if value <= -flick_in {
+                *state = -1;
+                return (true, false);
+            }
+            if value >= flick_in {
+                *state = 1;
+                return (false, true);
+            }
+        } else if value.abs() < flick_out {
+            *state = 0;
+        }
+        (false, false)
+    }
+
+    fn accept_just_pressed(&self) -> bool {
+        self.a_just_pressed || self.start_just_pressed
+    }
+
+    fn back_just_pressed(&self) -> bool {
+        self.b_just_pressed
+    }
+}
+
+// ============================================================================
+// FRAME INPUT
+// ============================================================================
+
+/// Every macroquad edge-triggered key read, plus mouse delta/wheel, folded
+/// in once per real frame rather than read fresh inside `update`/
+/// `update_player`. macroquad only refreshes `is_key_pressed`/
+/// `mouse_delta_position`/`mouse_wheel` once per rendered frame, but the
+/// fixed-timestep loop in `main` can run `update` zero, one, or several
+/// times in a single frame (zero on any display faster than ~60Hz, two or
+/// more after a hitch) - so this accumulates pending input across however
+/// many real frames pass until a tick actually consumes it, instead of
+/// either replaying the same edge/motion on a second tick in one frame or
+/// silently dropping it on a frame that advances no tick at all.
+#[derive(Default)]
+struct FrameInput {
+    mouse_delta: Vec2,
+    mouse_wheel: f32,
+    key_c: bool,
+    key_enter: bool,
+    key_space: bool,
+    key_o: bool,
+    key_tab: bool,
+    key_escape: bool,
+    key_t: bool,
+    key_up: bool,
+    key_down: bool,
+    key_left: bool,
+    key_right: bool,
+    key_w: bool,
+    key_a: bool,
+    key_s: bool,
+    key_d: bool,
+    key_1: bool,
+    key_2: bool,
+    key_3: bool,
+    key_4: bool,
+    key_5: bool,
+    key_r: bool,
+}
+
+impl FrameInput {
+    /// Folds this real frame's edge/delta reads into whatever is already
+    /// pending from earlier un-consumed frames. Call once per real frame,
+    /// before the fixed-timestep loop, not inside it.
+    fn accumulate(&mut self) {
+        self.mouse_delta += mouse_delta_position();
+        self.mouse_wheel += mouse_wheel().1;
+        self.key_c |= is_key_pressed(KeyCode::C);
+        self.key_enter |= is_key_pressed(KeyCode::Enter);
+        self.key_space |= is_key_pressed(KeyCode::Space);
+        self.key_o |= is_key_pressed(KeyCode::O);
+        self.key_tab |= is_key_pressed(KeyCode::Tab);
+        self.key_escape |= is_key_pressed(KeyCode::Escape);
+        self.key_t |= is_key_pressed(KeyCode::T);
+        self.key_up |= is_key_pressed(KeyCode::Up);
+        self.key_down |= is_key_pressed(KeyCode::Down);
+        self.key_left |= is_key_pressed(KeyCode::Left);
+        self.key_right |= is_key_pressed(KeyCode::Right);
+        self.key_w |= is_key_pressed(KeyCode::W);
+        self.key_a |= is_key_pressed(KeyCode::A);
+        self.key_s |= is_key_pressed(KeyCode::S);
+        self.key_d |= is_key_pressed(KeyCode::D);
+        self.key_1 |= is_key_pressed(KeyCode::Key1);
+        self.key_2 |= is_key_pressed(KeyCode::Key2);
+        self.key_3 |= is_key_pressed(KeyCode::Key3);
+        self.key_4 |= is_key_pressed(KeyCode::Key4);
+        self.key_5 |= is_key_pressed(KeyCode::Key5);
+        self.key_r |= is_key_pressed(KeyCode::R);
+    }
+
+    /// Zeroes every edge/delta after a tick has consumed it, so a tick only
+    /// ever sees input that arrived since the last one it consumed.
+    fn consume(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// ============================================================================
+// DETERMINISTIC RNG
+// ============================================================================
+// macroquad's global `rand::gen_range` makes runs non-reproducible. These two
+// small PRNGs let a run be replayed bit-for-bit from a fixed master seed.
+
+/// Master seeder carried on `World`. Never consumed directly for gameplay
+/// randomness - only used to mint fresh per-shot/per-pickup generators.
+struct XorShift32 {
+    state: u32,
+}
+
+impl XorShift32 {
+    fn new(seed: u32) -> Self {
+        Self { state: if seed == 0 { 0xDEAD_BEEF } else { seed } }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+}
+
+/// Per-shot/per-projectile generator derived from the master seeder.
+struct Xoroshiro32PlusPlus {
+    s: [u16; 2],
+}
+
+impl Xoroshiro32PlusPlus {
+    fn new(seed: u32) -> Self {
+        let s0 = (seed >> 16) as u16;
+        let s1 = seed as u16;
+        Self { s: [s0, if s0 == 0 && s1 == 0 { 1 } else { s1 }] }
+    }
+
+    fn next_u16(&mut self) -> u16 {
+        let s0 = self.s[0];
+        let mut s1 = self.s[1];
+        let result = s0.wrapping_add(s1).rotate_left(9).wrapping_add(s0);
+        s1 ^= s0;
+        self.s[0] = s0.rotate_left(13) ^ s1 ^ (s1 << 5);
+        self.s[1] = s1.rotate_left(10);
+        result
+    }
+
+    fn next_f32_range(&mut self, lo: f32, hi: f32) -> f32 {
+        let frac = self.next_u16() as f32 / u16::MAX as f32;
+        lo + frac * (hi - lo)
     }
 }
 
@@ -68,6 +265,9 @@ impl GamepadState {
 // CONSTANTS
 // ============================================================================
 
+/// Default master seed used when a run isn't started from an explicit seed.
+const DEFAULT_SEED: u32 = 0x5EED_CAFE;
+
 const PLAYER_SPEED: f32 = 8.0;
 const PLAYER_SPRINT: f32 = 1.6;
 const MOUSE_SENS: f32 = 1.0;
@@ -76,6 +276,17 @@ const PLAYER_RADIUS: f32 = 0.3;
 const MAX_HEALTH: f32 = 100.0;
 const CELL_SIZE: f32 = 4.0;
 const WALL_HEIGHT: f32 = 4.0;
+const PROJECTILE_MAX_LIFE: f32 = 5.0;
+const PROJECTILE_RADIUS: f32 = 0.2;
+/// Sideways wobble applied to explosive projectiles each tick from their own
+/// `rng`, so rockets wander a little instead of flying a perfectly straight
+/// line.
+const ROCKET_WOBBLE_STRENGTH: f32 = 0.6;
+/// Fixed simulation timestep. `main`'s loop accumulates real frame time and
+/// steps `update` in whole multiples of this, so gameplay runs at a stable
+/// rate and `render_3d` interpolates between ticks for smooth visuals at
+/// any display refresh rate.
+const FIXED_DT: f32 = 1.0 / 60.0;
 
 // ============================================================================
 // GAME STRUCTS
@@ -88,6 +299,9 @@ enum GameState {
     Paused,
     Dead,
     Victory,
+    /// Settings screen, reachable from `Menu` or `Paused` and returning to
+    /// whichever of those it was opened from.
+    Options,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -96,6 +310,7 @@ enum WeaponType {
     Shotgun,
     MachineGun,
     Rocket,
+    Railgun,
 }
 
 struct Weapon {
@@ -103,36 +318,124 @@ struct Weapon {
     damage: f32,
     fire_rate: f32,
     spread: f32,
-    ammo: i32,
-    max_ammo: i32,
     pellets: i32,
     explosive: bool,
+    /// How many distinct enemies a single ray can pierce through in a
+    /// line before stopping. 1 = stops at the first enemy hit.
+    penetration: u32,
+    /// Damage multiplier applied per enemy already pierced - e.g. 0.7
+    /// means the second target takes 70% damage, the third 49%, etc.
+    penetration_falloff: f32,
+    /// If true, holding fire accumulates `charge` instead of firing on a
+    /// timer; the shot goes out (scaled by charge) on release.
+    chargeable: bool,
+    charge_time: f32,
+    overcharge_time: f32,
+    /// Seconds the fire button has been held this charge-up, 0 when idle.
+    charge: f32,
+    /// Seconds between passive reserve-ammo refills; 0 disables regen.
+    ammo_regen_time: f32,
+    ammo_regen_timer: f32,
+    /// Whether the player has unlocked this weapon yet. Only the pistol
+    /// starts owned; the rest are granted by `PickupType::WeaponUnlock`.
+    owned: bool,
+    magazine: i32,
+    max_magazine: i32,
+    /// Ammo held in reserve, refilled by ammo pickups. -1 = unlimited.
+    reserve: i32,
+    max_reserve: i32,
+    reloading: bool,
+    reload_time: f32,
+    reload_duration: f32,
     last_shot: f64,
 }
 
 impl Weapon {
     fn pistol() -> Self {
         Self { wtype: WeaponType::Pistol, damage: 25.0, fire_rate: 3.0, spread: 0.02,
-               ammo: -1, max_ammo: -1, pellets: 1, explosive: false, last_shot: 0.0 }
+               pellets: 1, explosive: false, penetration: 2, penetration_falloff: 0.65, owned: true,
+               chargeable: false, charge_time: 0.6, overcharge_time: 0.3, charge: 0.0,
+               ammo_regen_time: 0.0, ammo_regen_timer: 0.0,
+               magazine: 12, max_magazine: 12, reserve: -1, max_reserve: -1,
+               reloading: false, reload_time: 0.0, reload_duration: 1.0, last_shot: 0.0 }
     }
     fn shotgun() -> Self {
         Self { wtype: WeaponType::Shotgun, damage: 12.0, fire_rate: 1.2, spread: 0.12,
-               ammo: 24, max_ammo: 24, pellets: 8, explosive: false, last_shot: 0.0 }
+               pellets: 8, explosive: false, penetration: 1, penetration_falloff: 1.0, owned: false,
+               chargeable: false, charge_time: 0.6, overcharge_time: 0.3, charge: 0.0,
+               ammo_regen_time: 0.0, ammo_regen_timer: 0.0,
+               magazine: 8, max_magazine: 8, reserve: 16, max_reserve: 48,
+               reloading: false, reload_time: 0.0, reload_duration: 1.8, last_shot: 0.0 }
     }
     fn machinegun() -> Self {
         Self { wtype: WeaponType::MachineGun, damage: 10.0, fire_rate: 12.0, spread: 0.06,
-               ammo: 200, max_ammo: 200, pellets: 1, explosive: false, last_shot: 0.0 }
+               pellets: 1, explosive: false, penetration: 1, penetration_falloff: 1.0, owned: false,
+               chargeable: false, charge_time: 0.6, overcharge_time: 0.3, charge: 0.0,
+               // Sustained fire keeps the belt-fed reserve topped up a round at a time.
+               ammo_regen_time: 4.0, ammo_regen_timer: 0.0,
+               magazine: 30, max_magazine: 30, reserve: 170, max_reserve: 240,
+               reloading: false, reload_time: 0.0, reload_duration: 2.2, last_shot: 0.0 }
     }
     fn rocket() -> Self {
         Self { wtype: WeaponType::Rocket, damage: 250.0, fire_rate: 0.5, spread: 0.0,
-               ammo: 20, max_ammo: 20, pellets: 1, explosive: true, last_shot: 0.0 }
+               pellets: 1, explosive: true, penetration: 1, penetration_falloff: 1.0, owned: false,
+               chargeable: false, charge_time: 0.6, overcharge_time: 0.3, charge: 0.0,
+               ammo_regen_time: 0.0, ammo_regen_timer: 0.0,
+               magazine: 4, max_magazine: 4, reserve: 16, max_reserve: 32,
+               reloading: false, reload_time: 0.0, reload_duration: 2.5, last_shot: 0.0 }
+    }
+    fn railgun() -> Self {
+        Self { wtype: WeaponType::Railgun, damage: 90.0, fire_rate: 1.0, spread: 0.0,
+               pellets: 1, explosive: false, penetration: 1, penetration_falloff: 1.0, owned: false,
+               chargeable: true, charge_time: 0.9, overcharge_time: 0.4, charge: 0.0,
+               ammo_regen_time: 0.0, ammo_regen_timer: 0.0,
+               magazine: 6, max_magazine: 6, reserve: 12, max_reserve: 24,
+               reloading: false, reload_time: 0.0, reload_duration: 2.0, last_shot: 0.0 }
     }
     fn can_fire(&self, time: f64) -> bool {
-        time - self.last_shot >= 1.0 / self.fire_rate as f64 && (self.ammo > 0 || self.ammo < 0)
+        self.owned && !self.reloading && self.magazine > 0
+            && time - self.last_shot >= 1.0 / self.fire_rate as f64
     }
     fn fire(&mut self, time: f64) {
         self.last_shot = time;
-        if self.ammo > 0 { self.ammo -= 1; }
+        self.magazine -= 1;
+        if self.magazine <= 0 { self.start_reload(); }
+    }
+    /// Begins a timed reload if there's anything to gain from it. Called
+    /// automatically when the magazine runs dry, or manually via the
+    /// reload key.
+    fn start_reload(&mut self) {
+        if self.reloading || self.magazine >= self.max_magazine || self.reserve == 0 { return; }
+        self.reloading = true;
+        self.reload_time = 0.0;
+    }
+    /// Advances an in-progress reload; refills the magazine from reserve
+    /// once `reload_duration` has elapsed.
+    fn tick(&mut self, dt: f32) {
+        if !self.reloading { return; }
+        self.reload_time += dt;
+        if self.reload_time >= self.reload_duration {
+            self.reloading = false;
+            let needed = self.max_magazine - self.magazine;
+            if self.reserve < 0 {
+                self.magazine = self.max_magazine;
+            } else {
+                let take = needed.min(self.reserve);
+                self.magazine += take;
+                self.reserve -= take;
+            }
+        }
+    }
+    /// Passively trickles a round back into reserve over time - used by
+    /// weapons like the machine gun's belt feed. Runs every frame for every
+    /// owned weapon, not just the equipped one.
+    fn tick_regen(&mut self, dt: f32) {
+        if self.ammo_regen_time <= 0.0 || self.reserve < 0 || self.reserve >= self.max_reserve { return; }
+        self.ammo_regen_timer += dt;
+        if self.ammo_regen_timer >= self.ammo_regen_time {
+            self.ammo_regen_timer -= self.ammo_regen_time;
+            self.reserve = (self.reserve + 1).min(self.max_reserve);
+        }
     }
     fn name(&self) -> &str {
         match self.wtype {
@@ -140,11 +443,18 @@ impl Weapon {
             WeaponType::Shotgun => "SHOTGUN",
             WeaponType::MachineGun => "MACHINE GUN",
             WeaponType::Rocket => "ROCKET",
+            WeaponType::Railgun => "RAILGUN",
         }
     }
+    /// True once there's nothing left to fire or reload with - an empty
+    /// magazine mid-reload doesn't count, since it's about to refill.
+    fn is_out_of_ammo(&self) -> bool {
+        !self.reloading && self.magazine <= 0 && self.reserve == 0
+    }
 }
 
 struct Player {
+    id: usize,             // 0 = player one, 1 = player two (co-op)
     pos: Vec3,
     yaw: f32,
     pitch: f32,
@@ -161,33 +471,95 @@ struct Player {
     pickup_msg_time: f32,
     is_aiming: bool,       // Aiming down sights
     aim_transition: f32,   // 0.0 = hip, 1.0 = ADS
+    fire_held: bool,       // Was the fire input still down last frame? Drives charge-release edges.
+    /// Which sector hazard the player is currently standing in, if any.
+    env_effect: SectorEffect,
+    /// Cross-fades toward 1.0 while inside `env_effect`'s sector, toward
+    /// 0.0 outside it - same lerp-in/lerp-out shape as `aim_transition`.
+    env_tint: f32,
+    /// The last non-`None` sector the player was in, kept around purely so
+    /// `render_hud` can keep drawing that color while `env_tint` fades back
+    /// to 0 after `env_effect` has already snapped to `None` on exit.
+    env_tint_effect: SectorEffect,
+    /// Countdown to the next tick of periodic hazard damage.
+    env_damage_timer: f32,
+    /// Set by the `GODMODE` cheat - all incoming damage is ignored while true.
+    invulnerable: bool,
+    /// Debounced analog left/right trigger state - see `update_trigger_hysteresis`.
+    /// Once pressed, stays pressed until the trigger falls below the release
+    /// threshold, so a light touch right at the press point doesn't chatter.
+    trigger_aim_active: bool,
+    trigger_shoot_active: bool,
 }
 
 impl Player {
-    fn new(x: f32, z: f32) -> Self {
+    fn new(id: usize, x: f32, z: f32) -> Self {
         Self {
+            id,
             pos: vec3(x, PLAYER_HEIGHT, z),
             yaw: 0.0, pitch: 0.0, health: MAX_HEALTH, armor: 0.0,
-            weapons: vec![Weapon::pistol(), Weapon::shotgun(), Weapon::machinegun(), Weapon::rocket()],
+            weapons: vec![Weapon::pistol(), Weapon::shotgun(), Weapon::machinegun(), Weapon::rocket(), Weapon::railgun()],
             current_weapon: 0, score: 0, damage_flash: 0.0,
             speed_boost: 0.0, damage_boost: 0.0, kills: 0,
             pickup_msg: String::new(), pickup_msg_time: 0.0,
             is_aiming: false, aim_transition: 0.0,
+            fire_held: false,
+            env_effect: SectorEffect::None, env_tint: 0.0, env_tint_effect: SectorEffect::None, env_damage_timer: 0.0,
+            invulnerable: false,
+            trigger_aim_active: false, trigger_shoot_active: false,
         }
     }
+
+    fn alive(&self) -> bool {
+        self.health > 0.0
+    }
     fn forward(&self) -> Vec3 {
         vec3(self.yaw.cos() * self.pitch.cos(), self.pitch.sin(), self.yaw.sin() * self.pitch.cos())
     }
     fn right(&self) -> Vec3 {
         vec3((self.yaw + PI/2.0).cos(), 0.0, (self.yaw + PI/2.0).sin())
     }
+
+    /// Steps `current_weapon` in `dir` (+1/-1), skipping owned-but-empty
+    /// and not-yet-unlocked guns, and stopping on the first usable one.
+    /// Returns `false` (leaving `current_weapon` untouched) if a full loop
+    /// finds nothing to switch to, and flashes a "no ammo" message.
+    fn next_weapon(&mut self, dir: i32) -> bool {
+        let n = self.weapons.len() as i32;
+        let mut i = self.current_weapon as i32;
+        for _ in 0..n {
+            i = (i + dir).rem_euclid(n);
+            let w = &self.weapons[i as usize];
+            if w.owned && !w.is_out_of_ammo() {
+                self.current_weapon = i as usize;
+                return true;
+            }
+        }
+        self.pickup_msg = "NO AMMO".to_string();
+        self.pickup_msg_time = 1.0;
+        false
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
 enum EnemyType { Grunt, Heavy, Demon }
 
+/// The pose `draw_3d_clarkson` blends toward, set from gameplay events
+/// rather than driven purely off the looping walk cycle.
+#[derive(Clone, Copy, PartialEq)]
+enum AnimState {
+    Walking,
+    Attacking,
+    Hurt,
+    Dying,
+}
+
 struct Enemy {
     pos: Vec3,
+    /// Position at the start of the current fixed update tick, for
+    /// `render_3d` to interpolate toward `pos` and smooth out any mismatch
+    /// between simulation rate and display refresh rate.
+    prev_pos: Vec3,
     health: f32,
     max_health: f32,
     etype: EnemyType,
@@ -197,20 +569,31 @@ struct Enemy {
     last_attack: f64,
     dead: bool,
     death_time: f64,
+    anim_state: AnimState,
+    /// Blends from the previous pose to `anim_state`'s target pose - 0 at
+    /// the moment the state changes, ramping to 1 (and back to 0 once
+    /// `anim_hold` runs out and the state reverts to Walking).
+    anim_transition: f32,
+    /// Seconds left before a transient state (Attacking/Hurt) reverts to
+    /// Walking. Unused for Dying, which is terminal.
+    anim_hold: f32,
 }
 
 impl Enemy {
     fn grunt(x: f32, z: f32) -> Self {
-        Self { pos: vec3(x, 1.0, z), health: 50.0, max_health: 50.0, etype: EnemyType::Grunt,
-               speed: 3.5, damage: 10.0, attack_cd: 1.0, last_attack: 0.0, dead: false, death_time: 0.0 }
+        Self { pos: vec3(x, 1.0, z), prev_pos: vec3(x, 1.0, z), health: 50.0, max_health: 50.0, etype: EnemyType::Grunt,
+               speed: 3.5, damage: 10.0, attack_cd: 1.0, last_attack: 0.0, dead: false, death_time: 0.0,
+               anim_state: AnimState::Walking, anim_transition: 0.0, anim_hold: 0.0 }
     }
     fn heavy(x: f32, z: f32) -> Self {
-        Self { pos: vec3(x, 1.5, z), health: 150.0, max_health: 150.0, etype: EnemyType::Heavy,
-               speed: 1.8, damage: 25.0, attack_cd: 1.5, last_attack: 0.0, dead: false, death_time: 0.0 }
+        Self { pos: vec3(x, 1.5, z), prev_pos: vec3(x, 1.5, z), health: 150.0, max_health: 150.0, etype: EnemyType::Heavy,
+               speed: 1.8, damage: 25.0, attack_cd: 1.5, last_attack: 0.0, dead: false, death_time: 0.0,
+               anim_state: AnimState::Walking, anim_transition: 0.0, anim_hold: 0.0 }
     }
     fn demon(x: f32, z: f32) -> Self {
-        Self { pos: vec3(x, 1.2, z), health: 70.0, max_health: 70.0, etype: EnemyType::Demon,
-               speed: 6.0, damage: 15.0, attack_cd: 0.6, last_attack: 0.0, dead: false, death_time: 0.0 }
+        Self { pos: vec3(x, 1.2, z), prev_pos: vec3(x, 1.2, z), health: 70.0, max_health: 70.0, etype: EnemyType::Demon,
+               speed: 6.0, damage: 15.0, attack_cd: 0.6, last_attack: 0.0, dead: false, death_time: 0.0,
+               anim_state: AnimState::Walking, anim_transition: 0.0, anim_hold: 0.0 }
     }
     fn color(&self) -> Color {
         match self.etype {
@@ -225,24 +608,253 @@ impl Enemy {
     fn points(&self) -> i32 {
         match self.etype { EnemyType::Grunt => 100, EnemyType::Heavy => 300, EnemyType::Demon => 200 }
     }
+    /// Switches to a transient pose (Attacking/Hurt) that reverts to
+    /// Walking on its own after `hold` seconds, restarting the blend.
+    fn set_anim(&mut self, state: AnimState, hold: f32) {
+        if self.anim_state == AnimState::Dying { return; }
+        self.anim_state = state;
+        self.anim_hold = hold;
+        self.anim_transition = 0.0;
+    }
+    /// Advances the hold timer and the current<->target pose blend.
+    fn tick_anim(&mut self, dt: f32) {
+        if self.anim_hold > 0.0 {
+            self.anim_hold -= dt;
+            if self.anim_hold <= 0.0 && self.anim_state != AnimState::Dying {
+                self.anim_state = AnimState::Walking;
+            }
+        }
+        let target = if self.anim_state == AnimState::Walking { 0.0 } else { 1.0 };
+        let speed = 10.0;
+        if self.anim_transition < target {
+            self.anim_transition = (self.anim_transition + dt * speed).min(target);
+        } else {
+            self.anim_transition = (self.anim_transition - dt * speed).max(target);
+        }
+    }
 }
 
 struct Projectile {
     pos: Vec3,
+    /// Position at the start of the current fixed update tick, lerped
+    /// toward `pos` by `render_3d` for smooth motion between ticks.
+    prev_pos: Vec3,
     vel: Vec3,
     damage: f32,
     explosive: bool,
+    owner: usize,          // index of the firing player, for kill/score credit
+    rng: Xoroshiro32PlusPlus,
+    life: f32,
+    max_life: f32,
+}
+
+impl Projectile {
+    /// Samples the level geometry at the projectile's current position and
+    /// reports whether it has struck a wall or the floor beneath it.
+    fn tick_map_collisions(&self, level: &Level) -> bool {
+        if level.check_collision(self.pos.x, self.pos.z, PROJECTILE_RADIUS) {
+            return true;
+        }
+        self.pos.y <= level.get_floor_height(self.pos.x, self.pos.z)
+    }
+}
+
+/// Owns live projectiles plus a staging buffer for ones spawned this frame,
+/// mirroring how a bullet manager seeds and ticks each bullet.
+struct ProjectileManager {
+    projectiles: Vec<Projectile>,
+    new_projectiles: Vec<Projectile>,
+}
+
+impl ProjectileManager {
+    fn new() -> Self {
+        Self { projectiles: Vec::new(), new_projectiles: Vec::new() }
+    }
+
+    fn spawn(&mut self, proj: Projectile) {
+        self.new_projectiles.push(proj);
+    }
+
+    fn clear(&mut self) {
+        self.projectiles.clear();
+        self.new_projectiles.clear();
+    }
+
+    /// Advances every live projectile by `dt`, resolving wall/floor impacts
+    /// and expiry, and returns `(pos, damage, owner)` for each one that
+    /// should detonate so the caller can apply area damage, credit the
+    /// right player, and spawn particles.
+    fn tick(&mut self, dt: f32, level: &Level, enemies: &mut [Enemy]) -> Vec<(Vec3, f32, usize)> {
+        self.projectiles.append(&mut self.new_projectiles);
+
+        let mut explosions: Vec<(Vec3, f32, usize)> = Vec::new();
+
+        self.projectiles.retain_mut(|proj| {
+            proj.prev_pos = proj.pos;
+            proj.pos += proj.vel * dt;
+            if proj.explosive {
+                // Rockets wander a little in flight rather than tracing a
+                // perfectly straight line, using the projectile's own rng so
+                // each shot wobbles independently and a fixed seed replays
+                // the same wander.
+                let up = vec3(0.0, 1.0, 0.0);
+                let lateral = proj.vel.normalize().cross(up);
+                let wobble = proj.rng.next_f32_range(-1.0, 1.0);
+                proj.pos += lateral * wobble * ROCKET_WOBBLE_STRENGTH * dt;
+            }
+            proj.life -= dt;
+
+            if proj.life <= 0.0 || proj.tick_map_collisions(level) {
+                if proj.explosive { explosions.push((proj.pos, proj.damage, proj.owner)); }
+                return false;
+            }
+
+            for enemy in enemies.iter_mut() {
+                if enemy.dead { continue; }
+                if (proj.pos - enemy.pos).length() < 1.0 {
+                    if proj.explosive { explosions.push((proj.pos, proj.damage, proj.owner)); }
+                    else { enemy.health -= proj.damage; }
+                    return false;
+                }
+            }
+            true
+        });
+
+        explosions
+    }
 }
 
 struct Particle {
     pos: Vec3,
+    /// Position at the start of the current fixed update tick, lerped
+    /// toward `pos` by `render_3d` for smooth motion between ticks.
+    prev_pos: Vec3,
     vel: Vec3,
     color: Color,
     life: f32,
     max_life: f32,
     size: f32,
+    /// Multiplier on the usual downward pull - 1.0 for normal debris, much
+    /// smaller for ambient motes that should drift rather than drop.
+    gravity_scale: f32,
+}
+
+/// A semantically-named visual effect. Each variant owns its own animation
+/// curve, default lifetime, billboard size and particle burst, so call sites
+/// just say *what* happened instead of poking scalar flash timers by hand.
+#[derive(Clone, Copy, PartialEq)]
+enum EffectType {
+    MuzzleFlash,
+    BulletImpact,
+    BloodSpray,
+    Explosion,
+    ShellCasing,
+    PickupSparkle,
+    /// Gold burst for a confirmed headshot, distinct from a torso `BloodSpray`.
+    Headshot,
+    /// Duller grey-red burst for a leg hit.
+    LimbHit,
+}
+
+impl EffectType {
+    fn max_life(&self) -> f32 {
+        match self {
+            EffectType::MuzzleFlash => 0.08,
+            EffectType::BulletImpact => 0.25,
+            EffectType::BloodSpray => 0.4,
+            EffectType::Explosion => 1.2,
+            EffectType::ShellCasing => 0.6,
+            EffectType::PickupSparkle => 0.5,
+            EffectType::Headshot => 0.45,
+            EffectType::LimbHit => 0.35,
+        }
+    }
+
+    fn billboard_size(&self) -> f32 {
+        match self {
+            EffectType::MuzzleFlash => 0.3,
+            EffectType::BulletImpact => 0.15,
+            EffectType::BloodSpray => 0.2,
+            EffectType::Explosion => 1.0,
+            EffectType::ShellCasing => 0.08,
+            EffectType::PickupSparkle => 0.15,
+            EffectType::Headshot => 0.22,
+            EffectType::LimbHit => 0.18,
+        }
+    }
+
+    fn particle_count(&self) -> i32 {
+        match self {
+            EffectType::MuzzleFlash => 4,
+            EffectType::BulletImpact => 8,
+            EffectType::BloodSpray => 10,
+            EffectType::Explosion => 60,
+            EffectType::ShellCasing => 1,
+            EffectType::PickupSparkle => 15,
+            EffectType::Headshot => 16,
+            EffectType::LimbHit => 8,
+        }
+    }
+
+    fn particle_speed(&self) -> f32 {
+        match self {
+            EffectType::MuzzleFlash => 2.0,
+            EffectType::BulletImpact => 3.0,
+            EffectType::BloodSpray => 4.0,
+            EffectType::Explosion => 15.0,
+            EffectType::ShellCasing => 1.5,
+            EffectType::PickupSparkle => 5.0,
+            EffectType::Headshot => 5.0,
+            EffectType::LimbHit => 3.0,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            EffectType::MuzzleFlash => Color::new(1.0, 0.9, 0.4, 1.0),
+            EffectType::BulletImpact => GRAY,
+            EffectType::BloodSpray => Color::new(0.6, 0.0, 0.0, 1.0),
+            EffectType::Explosion => ORANGE,
+            EffectType::ShellCasing => Color::new(0.8, 0.7, 0.3, 1.0),
+            EffectType::PickupSparkle => WHITE,
+            EffectType::Headshot => GOLD,
+            EffectType::LimbHit => Color::new(0.5, 0.25, 0.2, 1.0),
+        }
+    }
+}
+
+/// A live instance of an `EffectType`, aging toward `max_life` and then dying.
+struct Effect {
+    kind: EffectType,
+    pos: Vec3,
+    life: f32,
+    max_life: f32,
+}
+
+/// A fading railgun beam segment, drawn from `start` to `end` and culled
+/// once `BEAM_LIFETIME` seconds have passed since `birth_time`.
+struct Beam {
+    start: Vec3,
+    end: Vec3,
+    birth_time: f64,
+    color: Color,
+}
+
+const BEAM_LIFETIME: f32 = 0.4;
+
+/// A damage number rising out of a hit, culled once `FLOATING_TEXT_LIFETIME`
+/// seconds have passed since `birth_time`. `crit` (a headshot or a
+/// fully-charged shot) gets a larger, brighter style at render time.
+struct FloatingText {
+    world_pos: Vec3,
+    text: String,
+    birth_time: f64,
+    color: Color,
+    crit: bool,
 }
 
+const FLOATING_TEXT_LIFETIME: f32 = 0.9;
+
 #[derive(Clone, Copy, PartialEq)]
 enum PickupType {
     Health,
@@ -250,6 +862,8 @@ enum PickupType {
     SpeedBoost,
     DamageBoost,
     Armor,
+    /// Unlocks the next locked weapon in tier order (Pistol is always owned).
+    WeaponUnlock,
 }
 
 struct Pickup {
@@ -257,15 +871,48 @@ struct Pickup {
     pickup_type: PickupType,
     bob_offset: f32,
     collected: bool,
+    /// Current fall/bounce velocity. Zero once `settled` (or for pickups
+    /// that were placed on the map rather than dropped by a kill).
+    vel: Vec3,
+    /// Accumulated tumble angle for dropped loot still in flight.
+    spin: f32,
+    /// True once a dropped pickup has stopped bouncing and sits still.
+    /// Map-placed pickups start settled - they never had anywhere to fall.
+    settled: bool,
 }
 
 impl Pickup {
-    fn new(x: f32, z: f32, pickup_type: PickupType) -> Self {
+    fn new(x: f32, z: f32, pickup_type: PickupType, rng: &mut XorShift32) -> Self {
+        let mut jitter = Xoroshiro32PlusPlus::new(rng.next_u32());
         Self {
             pos: vec3(x, 0.5, z),
             pickup_type,
-            bob_offset: rand::gen_range(0.0, PI * 2.0),
+            bob_offset: jitter.next_f32_range(0.0, PI * 2.0),
+            collected: false,
+            vel: Vec3::ZERO,
+            spin: 0.0,
+            settled: true,
+        }
+    }
+
+    /// A loot drop flung out from a kill, per the cash-spawn launch: a
+    /// randomized horizontal kick plus an upward impulse, then left to
+    /// fall and bounce under `update_pickups`'s physics step.
+    fn dropped(pos: Vec3, pickup_type: PickupType, rng: &mut XorShift32) -> Self {
+        let mut jitter = Xoroshiro32PlusPlus::new(rng.next_u32());
+        let speed = 3.5;
+        Self {
+            pos,
+            pickup_type,
+            bob_offset: jitter.next_f32_range(0.0, PI * 2.0),
             collected: false,
+            vel: vec3(
+                jitter.next_f32_range(-speed * 0.3, speed * 0.3),
+                jitter.next_f32_range(speed * 0.6, speed),
+                jitter.next_f32_range(-speed * 0.3, speed * 0.3),
+            ),
+            spin: 0.0,
+            settled: false,
         }
     }
 
@@ -276,6 +923,7 @@ impl Pickup {
             PickupType::SpeedBoost => SKYBLUE,
             PickupType::DamageBoost => RED,
             PickupType::Armor => BLUE,
+            PickupType::WeaponUnlock => ORANGE,
         }
     }
 
@@ -286,15 +934,45 @@ impl Pickup {
             PickupType::SpeedBoost => "SPEED BOOST",
             PickupType::DamageBoost => "DAMAGE BOOST",
             PickupType::Armor => "ARMOR",
+            PickupType::WeaponUnlock => "NEW WEAPON",
         }
     }
 }
 
+/// Legend char -> enemy constructor. Adding a new enemy tile to a map pack
+/// is a one-line addition here, not a change to `spawn_enemies`.
+const ENEMY_LEGEND: &[(char, fn(f32, f32) -> Enemy)] = &[
+    ('G', Enemy::grunt),
+    ('H', Enemy::heavy),
+    ('D', Enemy::demon),
+];
+
+/// Legend char -> pickup type, mirroring `ENEMY_LEGEND`.
+const PICKUP_LEGEND: &[(char, PickupType)] = &[
+    ('+', PickupType::Health),
+    ('A', PickupType::Ammo),
+    ('S', PickupType::SpeedBoost),
+    ('B', PickupType::DamageBoost),
+    ('R', PickupType::Armor),
+    ('W', PickupType::WeaponUnlock),
+];
+
+/// A sector-wide environmental hazard a player stands in, queried via
+/// `Level::get_sector_effect` alongside `get_floor_height`/`check_collision`.
+#[derive(Clone, Copy, PartialEq, Default)]
+enum SectorEffect {
+    #[default]
+    None,
+    Water,
+    Hazard,
+}
+
 struct Level {
     width: usize,
     height: usize,
     grid: Vec<Vec<char>>,
     floor_heights: Vec<Vec<f32>>,  // Height of each cell's floor
+    sector_effects: Vec<Vec<SectorEffect>>,
     name: String,
 }
 
@@ -304,13 +982,115 @@ impl Level {
         vec![vec![h; width]; height]
     }
 
+    // Create a level with no sector effects (the common case)
+    fn uniform_effects(width: usize, height: usize) -> Vec<Vec<SectorEffect>> {
+        vec![vec![SectorEffect::None; width]; height]
+    }
+
+    /// Load a map pack from disk. Text format:
+    /// ```text
+    /// name = CUSTOM MAP
+    ///
+    /// [grid]
+    /// #########
+    /// #P..G..X#
+    /// #########
+    ///
+    /// [heights]
+    /// # optional "x y height" triples for raised platforms/ramps
+    /// 3 1 1.5
+    /// ```
+    /// Reuses the same char legend as the built-in levels (`#`, `P`, the
+    /// `ENEMY_LEGEND`/`PICKUP_LEGEND` tables, `X`). Returns `None` if the
+    /// file can't be read or has no `[grid]` section.
+    fn from_file(path: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+
+        let mut name = String::from("CUSTOM MAP");
+        let mut grid_lines: Vec<String> = Vec::new();
+        let mut height_overrides: Vec<(usize, usize, f32)> = Vec::new();
+        let mut effect_overrides: Vec<(usize, usize, SectorEffect)> = Vec::new();
+        let mut section = "";
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim_end();
+            let trimmed = line.trim();
+            if trimmed.is_empty() { continue; }
+            if section != "grid" && trimmed.starts_with('#') { continue; }
+
+            if trimmed == "[grid]" { section = "grid"; continue; }
+            if trimmed == "[heights]" { section = "heights"; continue; }
+            if trimmed == "[effects]" { section = "effects"; continue; }
+
+            if section.is_empty() {
+                if let Some(value) = trimmed.strip_prefix("name").and_then(|s| s.trim_start().strip_prefix('=')) {
+                    name = value.trim().to_string();
+                }
+                continue;
+            }
+
+            match section {
+                "grid" => grid_lines.push(line.to_string()),
+                "heights" => {
+                    let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                    if let [x, y, h] = parts[..] {
+                        if let (Ok(x), Ok(y), Ok(h)) = (x.parse::<usize>(), y.parse::<usize>(), h.parse::<f32>()) {
+                            height_overrides.push((x, y, h));
+                        }
+                    }
+                }
+                // "x y water" or "x y hazard" triples, mirroring [heights].
+                "effects" => {
+                    let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                    if let [x, y, kind] = parts[..] {
+                        let effect = match kind {
+                            "water" => Some(SectorEffect::Water),
+                            "hazard" => Some(SectorEffect::Hazard),
+                            _ => None,
+                        };
+                        if let (Ok(x), Ok(y), Some(effect)) = (x.parse::<usize>(), y.parse::<usize>(), effect) {
+                            effect_overrides.push((x, y, effect));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if grid_lines.is_empty() { return None; }
+
+        let width = grid_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let height = grid_lines.len();
+        let grid: Vec<Vec<char>> = grid_lines.iter().map(|l| {
+            let mut row: Vec<char> = l.chars().collect();
+            row.resize(width, '#');
+            row
+        }).collect();
+
+        let mut floor_heights = Self::uniform_heights(width, height, 0.0);
+        for (x, y, h) in height_overrides {
+            if y < height && x < width {
+                floor_heights[y][x] = h;
+            }
+        }
+
+        let mut sector_effects = Self::uniform_effects(width, height);
+        for (x, y, effect) in effect_overrides {
+            if y < height && x < width {
+                sector_effects[y][x] = effect;
+            }
+        }
+
+        Some(Self { width, height, grid, floor_heights, sector_effects, name })
+    }
+
     fn level_1() -> Self {
         let grid: Vec<Vec<char>> = vec![
             "####################",
             "#.G..........+.....#",
             "#..................#",
             "#......G.....G..A..#",
-            "#..................#",
+            "#........W.........#",
             "#...#......#.......#",
             "#...#..+...#...G...#",
             "#...#......#.......#",
@@ -328,7 +1108,10 @@ impl Level {
             "####################",
         ].iter().map(|s| s.chars().collect()).collect();
         let floor_heights = Self::uniform_heights(20, 20, 0.0);
-        Self { width: 20, height: 20, grid, floor_heights, name: "THE BEGINNING".into() }
+        // Shallow pond around the weapon-unlock tile
+        let mut sector_effects = Self::uniform_effects(20, 20);
+        for y in 3..=5 { for x in 8..=11 { sector_effects[y][x] = SectorEffect::Water; } }
+        Self { width: 20, height: 20, grid, floor_heights, sector_effects, name: "THE BEGINNING".into() }
     }
 
     fn level_2() -> Self {
@@ -341,7 +1124,7 @@ impl Level {
             "#.....H.......H.......D..#",
             "#........................#",
             "#...........S............#",
-            "#........................#",
+            "#............W...........#",
             "#..+.................+...#",
             "#..D..D..D..D..D..D......#",
             "#........................#",
@@ -355,7 +1138,10 @@ impl Level {
             "##########################",
         ].iter().map(|s| s.chars().collect()).collect();
         let floor_heights = Self::uniform_heights(26, 20, 0.0);
-        Self { width: 26, height: 20, grid, floor_heights, name: "DEMON'S LAIR".into() }
+        let mut sector_effects = Self::uniform_effects(26, 20);
+        for y in 7..=9 { for x in 11..=14 { sector_effects[y][x] = SectorEffect::Water; } }
+        for y in 14..=16 { for x in 10..=15 { sector_effects[y][x] = SectorEffect::Hazard; } }
+        Self { width: 26, height: 20, grid, floor_heights, sector_effects, name: "DEMON'S LAIR".into() }
     }
 
     fn level_3() -> Self {
@@ -369,7 +1155,7 @@ impl Level {
             "#..G.G.G.G.G.G.G.G.G.G.G.G...#",
             "#............................#",
             "#..+........S...........+....#",
-            "#............................#",
+            "#............W...............#",
             "#..D..D..D..D..D..D..D..D....#",
             "#............................#",
             "#..A........B...........A....#",
@@ -383,7 +1169,9 @@ impl Level {
             "##############################",
         ].iter().map(|s: &&str| s.chars().collect()).collect();
         let floor_heights = Self::uniform_heights(30, 21, 0.0);
-        Self { width: 30, height: 21, grid, floor_heights, name: "THE GAUNTLET".into() }
+        let mut sector_effects = Self::uniform_effects(30, 21);
+        for y in 8..=10 { for x in 11..=14 { sector_effects[y][x] = SectorEffect::Water; } }
+        Self { width: 30, height: 21, grid, floor_heights, sector_effects, name: "THE GAUNTLET".into() }
     }
 
     fn level_4() -> Self {
@@ -435,7 +1223,11 @@ impl Level {
             floor_heights[y][9] = 0.75; // ramp
         }
 
-        Self { width: 36, height: 21, grid, floor_heights, name: "THE MAZE OF CLARKSON".into() }
+        // Open lanes between the raised platform blocks flood with hazard muck
+        let mut sector_effects = Self::uniform_effects(36, 21);
+        for y in 8..=10 { for x in 1..35 { sector_effects[y][x] = SectorEffect::Hazard; } }
+
+        Self { width: 36, height: 21, grid, floor_heights, sector_effects, name: "THE MAZE OF CLARKSON".into() }
     }
 
     fn level_5() -> Self {
@@ -475,7 +1267,11 @@ impl Level {
         for y in 12..=13 { for x in 1..39 { floor_heights[y][x] = 0.5; } }
         // Lower levels stay at 0
 
-        Self { width: 40, height: 21, grid, floor_heights, name: "CLARKSON'S FINAL STAND".into() }
+        // The lowest tier floods with hazard as the last stand wears on
+        let mut sector_effects = Self::uniform_effects(40, 21);
+        for y in 14..=17 { for x in 1..39 { sector_effects[y][x] = SectorEffect::Hazard; } }
+
+        Self { width: 40, height: 21, grid, floor_heights, sector_effects, name: "CLARKSON'S FINAL STAND".into() }
     }
 
     fn is_wall(&self, x: i32, y: i32) -> bool {
@@ -493,6 +1289,18 @@ impl Level {
         }
     }
 
+    /// The hazard/water flag of the sector `(x, z)` sits in, or `None` if
+    /// it's outside the grid or just plain floor.
+    fn get_sector_effect(&self, x: f32, z: f32) -> SectorEffect {
+        let gx = (x / CELL_SIZE) as usize;
+        let gz = (z / CELL_SIZE) as usize;
+        if gz < self.height && gx < self.width {
+            self.sector_effects[gz][gx]
+        } else {
+            SectorEffect::None
+        }
+    }
+
     fn check_collision(&self, x: f32, z: f32, radius: f32) -> bool {
         let gx = (x / CELL_SIZE) as i32;
         let gz = (z / CELL_SIZE) as i32;
@@ -517,14 +1325,155 @@ impl Level {
 // GAME WORLD
 // ============================================================================
 
+/// One entry in the level playlist: either a built-in level constructor or
+/// an external map-pack file discovered under `maps/`.
+enum LevelSlot {
+    Builtin(fn() -> Level),
+    File(String),
+}
+
+/// Discover map-pack files under `maps/` (by extension), sorted by name so
+/// the playlist order is stable. Returns an empty list if the directory is
+/// missing, which tells `World` to fall back to the built-in five levels.
+fn discover_maps() -> Vec<String> {
+    let mut maps: Vec<String> = std::fs::read_dir("maps")
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().map(|ext| ext == "map" || ext == "txt").unwrap_or(false))
+                .filter_map(|p| p.to_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    maps.sort();
+    maps
+}
+
+/// Live-tunable settings surfaced in `GameState::Options` - `update_player`
+/// and `render_3d` read these directly each frame, so nothing here needs a
+/// restart to take effect. `master_volume` is only stored and shown on the
+/// Options screen; the game plays no audio, so nothing reads it.
+struct Settings {
+    master_volume: f32,
+    mouse_sensitivity: f32,
+    stick_sensitivity: f32,
+    invert_y: bool,
+    fov: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            mouse_sensitivity: 1.0,
+            stick_sensitivity: 1.0,
+            invert_y: false,
+            fov: 70.0,
+        }
+    }
+}
+
+/// Tunable analog-input conditioning - radial stick dead zone/curve and
+/// trigger press hysteresis. Lives on `World` alongside `Settings` and is
+/// surfaced as rows on `GameState::Options`, same as everything else there.
+struct InputConfig {
+    /// Stick magnitude below this (0..1) reads as dead center.
+    stick_deadzone: f32,
+    /// Exponent applied to the deadzone-rescaled stick magnitude - 1.0 is
+    /// linear (the prior behavior), >1.0 softens small movements for finer
+    /// aim without losing full deflection at the edge.
+    stick_curve: f32,
+    /// Analog trigger value above which it reads as "pressed".
+    trigger_press: f32,
+    /// Analog trigger value it must fall back under before it can read as
+    /// "pressed" again - must stay below `trigger_press` or it'd never latch.
+    trigger_release: f32,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self {
+            stick_deadzone: 0.15,
+            stick_curve: 1.0,
+            trigger_press: 0.3,
+            trigger_release: 0.2,
+        }
+    }
+}
+
+/// Radial dead zone + response curve + saturation clamp for a 2D stick
+/// axis pair. Diagonals are shaped by magnitude rather than per-axis, so
+/// they don't get an unintended boost/penalty versus a cardinal push.
+fn apply_stick_shaping(x: f32, y: f32, deadzone: f32, curve: f32) -> (f32, f32) {
+    let mag = (x * x + y * y).sqrt();
+    if mag <= deadzone {
+        return (0.0, 0.0);
+    }
+    let rescaled = ((mag - deadzone) / (1.0 - deadzone)).min(1.0);
+    let shaped = rescaled.powf(curve);
+    let scale = shaped / mag;
+    (x * scale, y * scale)
+}
+
+/// Debounces an analog trigger into a digital "pressed" state with
+/// hysteresis, so a finger resting right at the threshold doesn't chatter
+/// shooting/aiming on and off.
+fn update_trigger_hysteresis(active: bool, raw: f32, press: f32, release: f32) -> bool {
+    if active {
+        raw > release
+    } else {
+        raw > press
+    }
+}
+
+/// Rows shown on `GameState::Options`, top to bottom - kept in sync with
+/// `render_options` and `adjust_option`.
+const OPTIONS_ROW_COUNT: usize = 11;
+
+/// Applies a left/right nudge to whichever Options row is selected.
+/// `increase` is true for right/"increment", false for left/"decrement".
+fn adjust_option(world: &mut World, row: usize, increase: bool) {
+    let dir = if increase { 1.0 } else { -1.0 };
+    match row {
+        0 => world.settings.master_volume = (world.settings.master_volume + dir * 0.1).clamp(0.0, 1.0),
+        1 => world.settings.mouse_sensitivity = (world.settings.mouse_sensitivity + dir * 0.1).clamp(0.1, 3.0),
+        2 => world.settings.stick_sensitivity = (world.settings.stick_sensitivity + dir * 0.1).clamp(0.1, 3.0),
+        3 => world.settings.invert_y = !world.settings.invert_y,
+        4 => world.settings.fov = (world.settings.fov + dir * 2.0).clamp(50.0, 110.0),
+        5 => world.stereo_enabled = !world.stereo_enabled,
+        6 => world.input.stick_deadzone = (world.input.stick_deadzone + dir * 0.02).clamp(0.0, 0.5),
+        7 => world.input.stick_curve = (world.input.stick_curve + dir * 0.1).clamp(0.5, 3.0),
+        // Press/release thresholds are kept at least 0.05 apart so the
+        // hysteresis in `update_trigger_hysteresis` never collapses to a
+        // single value and starts chattering.
+        8 => {
+            let min = world.input.trigger_release + 0.05;
+            world.input.trigger_press = (world.input.trigger_press + dir * 0.05).clamp(min, 0.95);
+        }
+        9 => {
+            let max = world.input.trigger_press - 0.05;
+            world.input.trigger_release = (world.input.trigger_release + dir * 0.05).clamp(0.0, max);
+        }
+        10 => world.stereo_mirror_hud = !world.stereo_mirror_hud,
+        _ => {}
+    }
+}
+
 struct World {
     player: Player,
+    player2: Option<Player>,  // Some(..) when local co-op is active
+    coop_requested: bool,     // toggled from the menu, survives restarts
     enemies: Vec<Enemy>,
-    projectiles: Vec<Projectile>,
+    projectiles: ProjectileManager,
     particles: Vec<Particle>,
+    effects: Vec<Effect>,
+    beams: Vec<Beam>,
+    floating_texts: Vec<FloatingText>,
     pickups: Vec<Pickup>,
     level: Level,
     current_level: usize,
+    playlist: Vec<LevelSlot>,
     state: GameState,
     screen_shake: f32,
     muzzle_flash: f32,
@@ -533,20 +1482,66 @@ struct World {
     combo: i32,
     combo_timer: f32,
     total_kills: i32,
+    seeder: XorShift32,
+    seed: u32,
+    emitter_timers: EmitterTimers,
+    /// Leftover real time not yet consumed by a fixed `update` step - see
+    /// `FIXED_DT`. `render_3d` reads `accumulator / FIXED_DT` as the
+    /// interpolation alpha between each moving entity's `prev_pos` and `pos`.
+    accumulator: f32,
+    /// Rolling buffer of the last `CHEAT_BUFFER_LEN` alphanumeric keys typed,
+    /// checked each frame for a `CHEATS` table suffix match.
+    cheat_buffer: String,
+    /// Side-by-side stereo 3D toggle - when set in solo play, `main` renders
+    /// `render_3d` twice for player one, offset left/right by
+    /// `eye_separation / 2`. Co-op split screen ignores it.
+    stereo_enabled: bool,
+    /// Lateral distance between the two stereo cameras, in world units.
+    eye_separation: f32,
+    /// When stereo is enabled: draw the HUD once into each half instead of
+    /// a single centered pass over the whole window.
+    stereo_mirror_hud: bool,
+    settings: Settings,
+    input: InputConfig,
+    /// Selected row in the `GameState::Options` screen.
+    options_cursor: usize,
+    /// Where to return when Options is backed out of - `Menu` or `Paused`.
+    options_return_state: GameState,
+    /// Full-screen automap popup - freezes gameplay updates while open.
+    automap_open: bool,
 }
 
 impl World {
+    /// Starts from `RUST_BLASTER_SEED` when set and parseable, so a run can
+    /// be pinned to a chosen seed (e.g. to replay a bug report) without
+    /// touching code; otherwise falls back to `DEFAULT_SEED`.
     fn new() -> Self {
-        let level = Level::level_1();
+        let seed = std::env::var("RUST_BLASTER_SEED")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SEED);
+        Self::with_seed(seed)
+    }
+
+    /// Start a run from an explicit master seed, so it can be replayed.
+    fn with_seed(seed: u32) -> Self {
+        let playlist = Self::build_playlist();
+        let level = Self::load_level_slot(&playlist[0]);
         let (px, pz) = Self::find_char(&level, 'P');
         let mut world = Self {
-            player: Player::new(px, pz),
+            player: Player::new(0, px, pz),
+            player2: None,
+            coop_requested: false,
             enemies: Vec::new(),
-            projectiles: Vec::new(),
+            projectiles: ProjectileManager::new(),
             particles: Vec::new(),
+            effects: Vec::new(),
+            beams: Vec::new(),
+            floating_texts: Vec::new(),
             pickups: Vec::new(),
             level,
             current_level: 1,
+            playlist,
             state: GameState::Menu,
             screen_shake: 0.0,
             muzzle_flash: 0.0,
@@ -555,12 +1550,49 @@ impl World {
             combo: 0,
             combo_timer: 0.0,
             total_kills: 0,
+            seeder: XorShift32::new(seed),
+            seed,
+            emitter_timers: EmitterTimers::new(),
+            accumulator: 0.0,
+            cheat_buffer: String::with_capacity(CHEAT_BUFFER_LEN),
+            stereo_enabled: false,
+            eye_separation: 0.2,
+            stereo_mirror_hud: false,
+            settings: Settings::default(),
+            input: InputConfig::default(),
+            options_cursor: 0,
+            options_return_state: GameState::Menu,
+            automap_open: false,
         };
         world.spawn_enemies();
         world.spawn_pickups();
         world
     }
 
+    /// Build the level playlist from `maps/` map-pack files, falling back to
+    /// the five built-in levels when no map pack is present.
+    fn build_playlist() -> Vec<LevelSlot> {
+        let discovered = discover_maps();
+        if discovered.is_empty() {
+            vec![
+                LevelSlot::Builtin(Level::level_1),
+                LevelSlot::Builtin(Level::level_2),
+                LevelSlot::Builtin(Level::level_3),
+                LevelSlot::Builtin(Level::level_4),
+                LevelSlot::Builtin(Level::level_5),
+            ]
+        } else {
+            discovered.into_iter().map(LevelSlot::File).collect()
+        }
+    }
+
+    fn load_level_slot(slot: &LevelSlot) -> Level {
+        match slot {
+            LevelSlot::Builtin(ctor) => ctor(),
+            LevelSlot::File(path) => Level::from_file(path).unwrap_or_else(Level::level_1),
+        }
+    }
+
     fn find_char(level: &Level, c: char) -> (f32, f32) {
         for (y, row) in level.grid.iter().enumerate() {
             for (x, &cell) in row.iter().enumerate() {
@@ -572,54 +1604,111 @@ impl World {
         (CELL_SIZE * 2.0, CELL_SIZE * 2.0)
     }
 
+    /// Spawn point for player two. Maps that don't define a `Q` tile fall
+    /// back to player one's spawn so co-op still works on single-player maps.
+    fn find_player2_spawn(level: &Level) -> (f32, f32) {
+        for (y, row) in level.grid.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                if cell == 'Q' {
+                    return (x as f32 * CELL_SIZE + CELL_SIZE/2.0, y as f32 * CELL_SIZE + CELL_SIZE/2.0);
+                }
+            }
+        }
+        Self::find_char(level, 'P')
+    }
+
+    /// Index range over the players currently in the run: just player one,
+    /// or both when co-op is active.
+    fn player_indices(&self) -> std::ops::Range<usize> {
+        0..(if self.player2.is_some() { 2 } else { 1 })
+    }
+
+    fn player_ref(&self, idx: usize) -> &Player {
+        if idx == 0 { &self.player } else { self.player2.as_ref().expect("player two not active") }
+    }
+
+    fn player_mut(&mut self, idx: usize) -> &mut Player {
+        if idx == 0 { &mut self.player } else { self.player2.as_mut().expect("player two not active") }
+    }
+
+    /// True once every active player has fallen.
+    fn all_players_dead(&self) -> bool {
+        self.player_indices().all(|i| !self.player_ref(i).alive())
+    }
+
+    /// Index of the closest living player to `pos`, for enemy targeting.
+    fn nearest_player(&self, pos: Vec3) -> Option<usize> {
+        self.player_indices()
+            .filter(|&i| self.player_ref(i).alive())
+            .min_by(|&a, &b| {
+                let da = (self.player_ref(a).pos - pos).length_squared();
+                let db = (self.player_ref(b).pos - pos).length_squared();
+                da.partial_cmp(&db).unwrap()
+            })
+    }
+
     fn spawn_enemies(&mut self) {
         self.enemies.clear();
         for (y, row) in self.level.grid.iter().enumerate() {
             for (x, &cell) in row.iter().enumerate() {
                 let wx = x as f32 * CELL_SIZE + CELL_SIZE / 2.0;
                 let wz = y as f32 * CELL_SIZE + CELL_SIZE / 2.0;
-                match cell {
-                    'G' => self.enemies.push(Enemy::grunt(wx, wz)),
-                    'H' => self.enemies.push(Enemy::heavy(wx, wz)),
-                    'D' => self.enemies.push(Enemy::demon(wx, wz)),
-                    _ => {}
+                if let Some((_, ctor)) = ENEMY_LEGEND.iter().find(|(c, _)| *c == cell) {
+                    self.enemies.push(ctor(wx, wz));
                 }
             }
         }
     }
 
+    /// Flings a loot pickup out from a kill at `pos`, weighted by the
+    /// enemy's `points()` so tougher Clarksons drop the better stuff.
+    fn drop_loot(&mut self, pos: Vec3, points: i32) {
+        let weighted: &[(i32, PickupType)] = if points >= 300 {
+            &[(2, PickupType::Armor), (3, PickupType::Health), (2, PickupType::Ammo), (1, PickupType::DamageBoost)]
+        } else if points >= 200 {
+            &[(1, PickupType::Armor), (2, PickupType::Health), (3, PickupType::Ammo), (1, PickupType::SpeedBoost)]
+        } else {
+            &[(1, PickupType::Health), (3, PickupType::Ammo), (1, PickupType::SpeedBoost)]
+        };
+        let total: i32 = weighted.iter().map(|(w, _)| w).sum();
+        let mut roll_rng = Xoroshiro32PlusPlus::new(self.seeder.next_u32());
+        let roll = (roll_rng.next_u16() as u32 % total as u32) as i32;
+        let mut acc = 0;
+        let pickup_type = weighted.iter().find_map(|(w, t)| {
+            acc += w;
+            (roll < acc).then_some(*t)
+        }).unwrap_or(PickupType::Health);
+        self.pickups.push(Pickup::dropped(pos, pickup_type, &mut self.seeder));
+    }
+
     fn spawn_pickups(&mut self) {
         self.pickups.clear();
-        for (y, row) in self.level.grid.iter().enumerate() {
+        let grid = self.level.grid.clone();
+        for (y, row) in grid.iter().enumerate() {
             for (x, &cell) in row.iter().enumerate() {
                 let wx = x as f32 * CELL_SIZE + CELL_SIZE / 2.0;
                 let wz = y as f32 * CELL_SIZE + CELL_SIZE / 2.0;
-                match cell {
-                    '+' => self.pickups.push(Pickup::new(wx, wz, PickupType::Health)),
-                    'A' => self.pickups.push(Pickup::new(wx, wz, PickupType::Ammo)),
-                    'S' => self.pickups.push(Pickup::new(wx, wz, PickupType::SpeedBoost)),
-                    'B' => self.pickups.push(Pickup::new(wx, wz, PickupType::DamageBoost)),
-                    'R' => self.pickups.push(Pickup::new(wx, wz, PickupType::Armor)),
-                    _ => {}
+                if let Some((_, pickup_type)) = PICKUP_LEGEND.iter().find(|(c, _)| *c == cell) {
+                    self.pickups.push(Pickup::new(wx, wz, *pickup_type, &mut self.seeder));
                 }
             }
         }
     }
 
     fn load_level(&mut self, num: usize) {
-        self.level = match num {
-            1 => Level::level_1(),
-            2 => Level::level_2(),
-            3 => Level::level_3(),
-            4 => Level::level_4(),
-            5 => Level::level_5(),
-            _ => Level::level_1(),
-        };
+        let idx = num.saturating_sub(1).min(self.playlist.len().saturating_sub(1));
+        self.level = Self::load_level_slot(&self.playlist[idx]);
         self.current_level = num;
         let (px, pz) = Self::find_char(&self.level, 'P');
         self.player.pos = vec3(px, PLAYER_HEIGHT, pz);
         self.player.yaw = 0.0;
         self.player.pitch = 0.0;
+        if let Some(p2) = &mut self.player2 {
+            let (qx, qz) = Self::find_player2_spawn(&self.level);
+            p2.pos = vec3(qx, PLAYER_HEIGHT, qz);
+            p2.yaw = 0.0;
+            p2.pitch = 0.0;
+        }
         self.spawn_enemies();
         self.spawn_pickups();
         self.projectiles.clear();
@@ -627,17 +1716,16 @@ impl World {
     }
 
     fn restart(&mut self) {
-        self.player.health = MAX_HEALTH;
-        self.player.armor = 0.0;
-        self.player.score = 0;
-        self.player.kills = 0;
-        self.player.speed_boost = 0.0;
-        self.player.damage_boost = 0.0;
-        self.player.weapons = vec![Weapon::pistol(), Weapon::shotgun(), Weapon::machinegun(), Weapon::rocket()];
-        self.player.current_weapon = 0;
+        self.player = Player::new(0, self.player.pos.x, self.player.pos.z);
+        self.player2 = if self.coop_requested {
+            Some(Player::new(1, self.player.pos.x, self.player.pos.z))
+        } else {
+            None
+        };
         self.combo = 0;
         self.combo_timer = 0.0;
         self.total_kills = 0;
+        self.automap_open = false;
         self.load_level(1);
         self.state = GameState::Playing;
     }
@@ -651,65 +1739,124 @@ impl World {
 // UPDATE
 // ============================================================================
 
-fn update(world: &mut World, dt: f32, gamepad: &GamepadState) {
+fn update(world: &mut World, dt: f32, gamepad: &GamepadState, gamepad2: &GamepadState, input: &FrameInput) {
     let time = get_time();
 
     // Decay effects
     world.screen_shake = (world.screen_shake - dt * 5.0).max(0.0);
     world.muzzle_flash = (world.muzzle_flash - dt * 10.0).max(0.0);
     world.hit_marker = (world.hit_marker - dt * 5.0).max(0.0);
-    world.player.damage_flash = (world.player.damage_flash - dt * 2.0).max(0.0);
+    for i in world.player_indices() {
+        world.player_mut(i).damage_flash = (world.player_mut(i).damage_flash - dt * 2.0).max(0.0);
+    }
 
     match world.state {
         GameState::Menu => {
-            // Start game with Enter, Space, or gamepad A/Start
-            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space)
-                || gamepad.a_just_pressed || gamepad.start_just_pressed {
+            // Toggle two-player local co-op before starting
+            if input.key_c {
+                world.coop_requested = !world.coop_requested;
+            }
+            // Start game with Enter, Space, or gamepad accept
+            if input.key_enter || input.key_space
+                || gamepad.accept_just_pressed() {
                 world.restart();
                 set_cursor_grab(true);
                 show_mouse(false);
             }
+            if input.key_o || gamepad.rb_just_pressed {
+                world.options_return_state = GameState::Menu;
+                world.state = GameState::Options;
+            }
         }
         GameState::Playing => {
-            update_player(world, dt, time, gamepad);
-            update_enemies(world, dt, time);
-            update_projectiles(world, dt);
-            update_particles(world, dt);
-            update_pickups(world, dt);
-            check_victory(world);
-
-            // Combo timer decay
-            world.combo_timer -= dt;
-            if world.combo_timer <= 0.0 {
-                world.combo = 0;
+            // Full-screen automap - toggling it freezes the simulation
+            // below like a popup, without leaving the Playing state.
+            if input.key_tab || gamepad.select_just_pressed {
+                world.automap_open = !world.automap_open;
             }
 
-            // Pause with ESC or Start button
-            if is_key_pressed(KeyCode::Escape) || gamepad.start_just_pressed {
-                world.state = GameState::Paused;
-                set_cursor_grab(false);
-                show_mouse(true);
-            }
-            if world.player.health <= 0.0 {
-                world.state = GameState::Dead;
-                set_cursor_grab(false);
-                show_mouse(true);
+            if world.automap_open {
+                if input.key_escape || gamepad.start_just_pressed {
+                    world.automap_open = false;
+                    world.state = GameState::Paused;
+                    set_cursor_grab(false);
+                    show_mouse(true);
+                }
+            } else {
+                update_player(world, 0, dt, time, gamepad, input);
+                if world.player2.is_some() {
+                    update_player(world, 1, dt, time, gamepad2, input);
+                }
+                update_enemies(world, dt, time);
+                update_projectiles(world, dt);
+                update_particles(world, dt);
+                update_emitters(world, dt);
+                update_effects(world, dt);
+                update_beams(world);
+                update_floating_texts(world);
+                update_pickups(world, dt);
+                check_victory(world);
+
+                // Combo timer decay
+                world.combo_timer -= dt;
+                if world.combo_timer <= 0.0 {
+                    world.combo = 0;
+                }
+
+                // Pause with ESC or Start button
+                if input.key_escape || gamepad.start_just_pressed {
+                    world.state = GameState::Paused;
+                    set_cursor_grab(false);
+                    show_mouse(true);
+                }
+
+                // Toggle side-by-side stereoscopic rendering
+                if input.key_t {
+                    world.stereo_enabled = !world.stereo_enabled;
+                }
+                if world.all_players_dead() {
+                    world.state = GameState::Dead;
+                    set_cursor_grab(false);
+                    show_mouse(true);
+                }
             }
         }
         GameState::Paused => {
             // Unpause with ESC or Start/B button
-            if is_key_pressed(KeyCode::Escape) || gamepad.start_just_pressed || gamepad.b_just_pressed {
+            if input.key_escape || gamepad.start_just_pressed || gamepad.back_just_pressed() {
                 world.state = GameState::Playing;
                 set_cursor_grab(true);
                 show_mouse(false);
             }
+            if input.key_o || gamepad.rb_just_pressed {
+                world.options_return_state = GameState::Paused;
+                world.state = GameState::Options;
+            }
+        }
+        GameState::Options => {
+            if gamepad.nav_up_just || input.key_up || input.key_w {
+                world.options_cursor = (world.options_cursor + OPTIONS_ROW_COUNT - 1) % OPTIONS_ROW_COUNT;
+            }
+            if gamepad.nav_down_just || input.key_down || input.key_s {
+                world.options_cursor = (world.options_cursor + 1) % OPTIONS_ROW_COUNT;
+            }
+            let left = gamepad.nav_left_just || input.key_left || input.key_a;
+            let right = gamepad.nav_right_just || input.key_right || input.key_d;
+            if left || right {
+                adjust_option(world, world.options_cursor, right);
+            }
+            if input.key_escape || gamepad.back_just_pressed() {
+                world.state = world.options_return_state;
+            }
         }
         GameState::Dead | GameState::Victory => {
-            // Continue with Enter, Space, or gamepad A/Start
-            if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space)
-                || gamepad.a_just_pressed || gamepad.start_just_pressed {
-                if world.state == GameState::Victory && world.current_level < 5 {
-                    world.player.kills = 0; // Reset kills for new level
+            // Continue with Enter, Space, or gamepad accept
+            if input.key_enter || input.key_space
+                || gamepad.accept_just_pressed() {
+                if world.state == GameState::Victory && world.current_level < world.playlist.len() {
+                    for i in world.player_indices() {
+                        world.player_mut(i).kills = 0; // Reset kills for new level
+                    }
                     world.load_level(world.current_level + 1);
                     world.state = GameState::Playing;
                     set_cursor_grab(true);
@@ -722,51 +1869,104 @@ fn update(world: &mut World, dt: f32, gamepad: &GamepadState) {
     }
 }
 
-fn update_player(world: &mut World, dt: f32, time: f64, gamepad: &GamepadState) {
+/// Drives one player's look/move/weapon/shoot input for this frame.
+/// Player one also reads keyboard + mouse; player two (co-op) is
+/// gamepad-only since they share the same keyboard and mouse cursor.
+fn update_player(world: &mut World, idx: usize, dt: f32, time: f64, gamepad: &GamepadState, input: &FrameInput) {
+    let uses_kbm = idx == 0;
+
     // === GAMEPAD INPUT ===
-    // Deadzone for sticks
-    let deadzone = 0.15;
-    let apply_deadzone = |v: f32| if v.abs() < deadzone { 0.0 } else { v };
+    // Radial dead zone + response curve on both sticks, tunable via
+    // world.input (see Options menu plumbing) rather than a flat per-axis cutoff.
+    let (gp_move_x, gp_move_y) = apply_stick_shaping(
+        gamepad.left_stick_x, gamepad.left_stick_y, world.input.stick_deadzone, world.input.stick_curve,
+    );
+    let (gp_look_x, gp_look_y) = apply_stick_shaping(
+        gamepad.right_stick_x, gamepad.right_stick_y, world.input.stick_deadzone, world.input.stick_curve,
+    );
 
-    let gp_move_x = apply_deadzone(gamepad.left_stick_x);
-    let gp_move_y = apply_deadzone(gamepad.left_stick_y);
-    let gp_look_x = apply_deadzone(gamepad.right_stick_x);
-    let gp_look_y = apply_deadzone(gamepad.right_stick_y);
+    // Triggers are debounced with hysteresis (see update_trigger_hysteresis)
+    // so a light touch right at the threshold doesn't misfire aim/shoot.
+    let trigger_press = world.input.trigger_press;
+    let trigger_release = world.input.trigger_release;
+    let lt_active = {
+        let p = world.player_mut(idx);
+        p.trigger_aim_active = update_trigger_hysteresis(p.trigger_aim_active, gamepad.left_trigger, trigger_press, trigger_release);
+        p.trigger_aim_active
+    };
 
     // Aiming down sights (right mouse button OR left trigger)
-    let trigger_aim = gamepad.left_trigger > 0.3 || gamepad.lt_button;
-    world.player.is_aiming = is_mouse_button_down(MouseButton::Right) || trigger_aim;
+    let trigger_aim = lt_active || gamepad.lt_button;
+    let is_aiming = (uses_kbm && is_mouse_button_down(MouseButton::Right)) || trigger_aim;
     let aim_speed = 6.0;
-    if world.player.is_aiming {
-        world.player.aim_transition = (world.player.aim_transition + dt * aim_speed).min(1.0);
+    let mut aim_transition = world.player_ref(idx).aim_transition;
+    if is_aiming {
+        aim_transition = (aim_transition + dt * aim_speed).min(1.0);
     } else {
-        world.player.aim_transition = (world.player.aim_transition - dt * aim_speed).max(0.0);
+        aim_transition = (aim_transition - dt * aim_speed).max(0.0);
     }
-    // Safety clamp
-    world.player.aim_transition = world.player.aim_transition.clamp(0.0, 1.0);
+    aim_transition = aim_transition.clamp(0.0, 1.0);
+    world.player_mut(idx).is_aiming = is_aiming;
+    world.player_mut(idx).aim_transition = aim_transition;
+
+    // Environmental sector (water/hazard) tint, cross-faded the same way
+    // aim_transition is - smoothly in when entering, smoothly out when
+    // leaving, rather than snapping with the tile boundary.
+    {
+        let player_pos = world.player_ref(idx).pos;
+        let sector = world.level.get_sector_effect(player_pos.x, player_pos.z);
+        let env_speed = 2.0;
+        let p = world.player_mut(idx);
+        p.env_effect = sector;
+        if sector != SectorEffect::None {
+            p.env_tint_effect = sector;
+            p.env_tint = (p.env_tint + dt * env_speed).min(1.0);
+        } else {
+            p.env_tint = (p.env_tint - dt * env_speed).max(0.0);
+        }
 
-    // Mouse look + right stick look
+        if sector == SectorEffect::Hazard {
+            p.env_damage_timer -= dt;
+            if p.env_damage_timer <= 0.0 {
+                p.env_damage_timer = 1.0;
+                if !p.invulnerable {
+                    p.health = (p.health - 5.0).max(0.0);
+                }
+                p.damage_flash = p.damage_flash.max(0.3);
+            }
+        } else {
+            p.env_damage_timer = 0.0;
+        }
+    }
+
+    // Mouse look (player one only) + right stick look (everyone)
     // Sensitivity reduced to 0.8x when ADS
-    let aim = world.player.aim_transition;
-    let ads_sens_mult = 1.0 - aim * 0.2; // 1.0 when not aiming, 0.8 when fully aimed
-    let delta = mouse_delta_position();
-    let gamepad_look_sens = 0.03 * ads_sens_mult;
-    let mouse_sens = MOUSE_SENS * ads_sens_mult;
-    world.player.yaw -= delta.x * mouse_sens;
-    world.player.yaw += gp_look_x * gamepad_look_sens * dt * 60.0;
-    world.player.pitch = (world.player.pitch + delta.y * mouse_sens + gp_look_y * gamepad_look_sens * dt * 60.0)
-        .clamp(-PI/2.0 + 0.1, PI/2.0 - 0.1);
+    let ads_sens_mult = 1.0 - aim_transition * 0.2; // 1.0 when not aiming, 0.8 when fully aimed
+    let delta = if uses_kbm { input.mouse_delta } else { Vec2::ZERO };
+    let invert = if world.settings.invert_y { -1.0 } else { 1.0 };
+    let gamepad_look_sens = 0.03 * ads_sens_mult * world.settings.stick_sensitivity;
+    let mouse_sens = MOUSE_SENS * ads_sens_mult * world.settings.mouse_sensitivity;
+    {
+        let p = world.player_mut(idx);
+        p.yaw -= delta.x * mouse_sens;
+        p.yaw += gp_look_x * gamepad_look_sens * dt * 60.0;
+        p.pitch = (p.pitch + delta.y * mouse_sens * invert + gp_look_y * gamepad_look_sens * dt * 60.0 * invert)
+            .clamp(-PI/2.0 + 0.1, PI/2.0 - 0.1);
+    }
 
     // Movement (keyboard + left stick)
     let mut move_dir = Vec3::ZERO;
-    let forward = vec3(world.player.yaw.cos(), 0.0, world.player.yaw.sin());
-    let right = world.player.right();
-
-    // Keyboard movement
-    if is_key_down(KeyCode::W) { move_dir += forward; }
-    if is_key_down(KeyCode::S) { move_dir -= forward; }
-    if is_key_down(KeyCode::A) { move_dir -= right; }
-    if is_key_down(KeyCode::D) { move_dir += right; }
+    let yaw = world.player_ref(idx).yaw;
+    let forward = vec3(yaw.cos(), 0.0, yaw.sin());
+    let right = world.player_ref(idx).right();
+
+    // Keyboard movement (player one only)
+    if uses_kbm {
+        if is_key_down(KeyCode::W) { move_dir += forward; }
+        if is_key_down(KeyCode::S) { move_dir -= forward; }
+        if is_key_down(KeyCode::A) { move_dir -= right; }
+        if is_key_down(KeyCode::D) { move_dir += right; }
+    }
 
     // Gamepad left stick movement
     move_dir += forward * gp_move_y;  // Forward/back
@@ -778,170 +1978,356 @@ fn update_player(world: &mut World, dt: f32, time: f64, gamepad: &GamepadState)
     if move_dir.length() > 0.0 {
         move_dir = move_dir.normalize();
         let mut speed = PLAYER_SPEED;
-        if is_key_down(KeyCode::LeftShift) || gamepad_sprint { speed *= PLAYER_SPRINT; }
-        if world.player.speed_boost > 0.0 { speed *= 1.5; } // Speed powerup!
+        if (uses_kbm && is_key_down(KeyCode::LeftShift)) || gamepad_sprint { speed *= PLAYER_SPRINT; }
+        if world.player_ref(idx).speed_boost > 0.0 { speed *= 1.5; } // Speed powerup!
+        if world.player_ref(idx).env_effect == SectorEffect::Water { speed *= 0.6; } // Wading through water
 
-        let new_x = world.player.pos.x + move_dir.x * speed * dt;
-        let new_z = world.player.pos.z + move_dir.z * speed * dt;
+        let cur_pos = world.player_ref(idx).pos;
+        let new_x = cur_pos.x + move_dir.x * speed * dt;
+        let new_z = cur_pos.z + move_dir.z * speed * dt;
 
-        if !world.level.check_collision(new_x, world.player.pos.z, PLAYER_RADIUS) {
-            world.player.pos.x = new_x;
+        if !world.level.check_collision(new_x, cur_pos.z, PLAYER_RADIUS) {
+            world.player_mut(idx).pos.x = new_x;
         }
-        if !world.level.check_collision(world.player.pos.x, new_z, PLAYER_RADIUS) {
-            world.player.pos.z = new_z;
+        if !world.level.check_collision(world.player_ref(idx).pos.x, new_z, PLAYER_RADIUS) {
+            world.player_mut(idx).pos.z = new_z;
         }
     }
 
     // Update player Y based on floor height (smooth transition)
-    let target_height = world.level.get_floor_height(world.player.pos.x, world.player.pos.z) + PLAYER_HEIGHT;
+    let pos = world.player_ref(idx).pos;
+    let target_height = world.level.get_floor_height(pos.x, pos.z) + PLAYER_HEIGHT;
     let height_lerp_speed = 10.0;
-    world.player.pos.y += (target_height - world.player.pos.y) * height_lerp_speed * dt;
+    world.player_mut(idx).pos.y += (target_height - pos.y) * height_lerp_speed * dt;
 
     // Weapon switching (keyboard, mouse wheel, or gamepad bumpers/d-pad)
-    if is_key_pressed(KeyCode::Key1) { world.player.current_weapon = 0; }
-    if is_key_pressed(KeyCode::Key2) { world.player.current_weapon = 1; }
-    if is_key_pressed(KeyCode::Key3) { world.player.current_weapon = 2; }
-    if is_key_pressed(KeyCode::Key4) { world.player.current_weapon = 3; }
-
-    let wheel = mouse_wheel().1;
-    if wheel > 0.0 { world.player.current_weapon = (world.player.current_weapon + 1) % 4; }
-    if wheel < 0.0 { world.player.current_weapon = (world.player.current_weapon + 3) % 4; }
+    let p = world.player_mut(idx);
+    if uses_kbm {
+        if input.key_1 { p.current_weapon = 0; }
+        if input.key_2 { p.current_weapon = 1; }
+        if input.key_3 { p.current_weapon = 2; }
+        if input.key_4 { p.current_weapon = 3; }
+        if input.key_5 { p.current_weapon = 4; }
+
+        if input.mouse_wheel > 0.0 { p.next_weapon(1); }
+        if input.mouse_wheel < 0.0 { p.next_weapon(-1); }
+    }
 
     // Gamepad weapon switching: RB = next, LB = previous, D-pad for direct select
     if gamepad.rb_just_pressed {
-        world.player.current_weapon = (world.player.current_weapon + 1) % 4;
+        p.next_weapon(1);
+    }
+    if gamepad.lb_just_pressed {
+        p.next_weapon(-1);
+    }
+    // D-pad for direct weapon select
+    if gamepad.dpad_up_just { p.current_weapon = 0; }
+    if gamepad.dpad_right_just { p.current_weapon = 1; }
+    if gamepad.dpad_down_just { p.current_weapon = 2; }
+    if gamepad.dpad_left_just { p.current_weapon = 3; }
+
+    // Manual reload (keyboard R or gamepad Y), plus the equipped weapon's
+    // own reload clock ticking toward completion.
+    let manual_reload = (uses_kbm && input.key_r) || gamepad.y_pressed;
+    let weapon = &mut p.weapons[p.current_weapon];
+    if manual_reload { weapon.start_reload(); }
+    weapon.tick(dt);
+    // Reserve regen runs for every owned weapon, not just the one in hand.
+    for weapon in p.weapons.iter_mut().filter(|w| w.owned) {
+        weapon.tick_regen(dt);
+    }
+
+    // Shooting (left click OR right trigger, debounced with hysteresis, OR A button)
+    p.trigger_shoot_active = update_trigger_hysteresis(p.trigger_shoot_active, gamepad.right_trigger, trigger_press, trigger_release);
+    let gamepad_shoot = p.trigger_shoot_active || gamepad.rt_button || gamepad.a_pressed;
+    let firing = (uses_kbm && is_mouse_button_down(MouseButton::Left)) || gamepad_shoot;
+    try_shoot(world, idx, time, dt, firing);
+}
+
+/// Handles one frame of trigger input for the player's equipped weapon.
+/// Non-chargeable weapons fire on a plain cooldown, same as before charging
+/// existed. Chargeable weapons instead accumulate `charge` while the
+/// trigger is held and let the shot go on release, scaled by how full the
+/// charge got; holding past `overcharge_time` vents the shot instead of
+/// firing it, so you can't just hold forever for a free max-power shot.
+fn try_shoot(world: &mut World, idx: usize, time: f64, dt: f32, firing: bool) {
+    let player = world.player_mut(idx);
+    let chargeable = player.weapons[player.current_weapon].chargeable;
+
+    if !chargeable {
+        player.fire_held = firing;
+        if !firing { return; }
+        let weapon = &mut player.weapons[player.current_weapon];
+        if !weapon.can_fire(time) { return; }
+        weapon.fire(time);
+        fire_weapon_shot(world, idx, 1.0);
+        return;
     }
-    if gamepad.lb_just_pressed {
-        world.player.current_weapon = (world.player.current_weapon + 3) % 4;
+
+    let weapon = &mut player.weapons[player.current_weapon];
+    let was_held = player.fire_held;
+    player.fire_held = firing;
+
+    if firing && weapon.can_fire(time) {
+        weapon.charge = (weapon.charge + dt).min(weapon.charge_time + weapon.overcharge_time);
     }
-    // D-pad for direct weapon select
-    if gamepad.dpad_up_just { world.player.current_weapon = 0; }
-    if gamepad.dpad_right_just { world.player.current_weapon = 1; }
-    if gamepad.dpad_down_just { world.player.current_weapon = 2; }
-    if gamepad.dpad_left_just { world.player.current_weapon = 3; }
 
-    // Shooting (left click OR right trigger OR A button)
-    let gamepad_shoot = gamepad.right_trigger > 0.3 || gamepad.rt_button || gamepad.a_pressed;
-    if is_mouse_button_down(MouseButton::Left) || gamepad_shoot {
-        try_shoot(world, time);
+    let released = was_held && !firing;
+    let overcharged = weapon.charge >= weapon.charge_time + weapon.overcharge_time;
+
+    if released && weapon.charge > 0.0 && weapon.can_fire(time) {
+        let charge_frac = (weapon.charge / weapon.charge_time).min(1.0);
+        weapon.fire(time);
+        weapon.charge = 0.0;
+        fire_weapon_shot(world, idx, charge_frac);
+    } else if overcharged {
+        weapon.charge = 0.0;
+        vent_overcharge(world, idx);
+    } else if !firing {
+        weapon.charge = 0.0;
     }
 }
 
-fn try_shoot(world: &mut World, time: f64) {
-    let weapon = &mut world.player.weapons[world.player.current_weapon];
-    if !weapon.can_fire(time) { return; }
+/// Punishment for holding a charge shot past `overcharge_time`: the charge
+/// vents harmlessly into the weapon instead of firing, but the player eats
+/// a small jolt of self-damage and a screen shake for not letting go.
+fn vent_overcharge(world: &mut World, idx: usize) {
+    world.screen_shake = 0.4;
+    let p = world.player_mut(idx);
+    if !p.invulnerable {
+        p.health = (p.health - 5.0).max(0.0);
+    }
+    p.damage_flash = p.damage_flash.max(0.3);
+}
 
-    weapon.fire(time);
+/// Fires the equipped weapon's pellets/rays/projectile, scaled by
+/// `charge_frac` (1.0 for a fully-held charge shot or any non-chargeable
+/// weapon - more damage, tighter spread, and, for explosives, a faster
+/// projectile). An overcharge vents without ever reaching this function,
+/// so it's the only place a chargeable weapon's damage gets applied.
+fn fire_weapon_shot(world: &mut World, idx: usize, charge_frac: f32) {
     world.muzzle_flash = 1.0;
     world.screen_shake = 0.1;
 
-    let mut damage = weapon.damage;
-    if world.player.damage_boost > 0.0 { damage *= 2.0; } // Damage powerup!
-    // Reduced spread when aiming (50% tighter)
-    let spread = weapon.spread * (1.0 - world.player.aim_transition * 0.5);
+    let player = world.player_ref(idx);
+    let weapon = &player.weapons[player.current_weapon];
+    let mut damage = weapon.damage * (0.5 + charge_frac * 0.5);
+    if player.damage_boost > 0.0 { damage *= 2.0; } // Damage powerup!
+    // Reduced spread when aiming (50% tighter), and tighter again the more charged the shot is.
+    let spread = weapon.spread * (1.0 - player.aim_transition * 0.5) * (1.0 - charge_frac * 0.5);
     let pellets = weapon.pellets;
     let explosive = weapon.explosive;
+    let beam = weapon.wtype == WeaponType::Railgun;
+    let beam_color = railgun_beam_color(charge_frac);
+    let penetration = weapon.penetration.max(1);
+    let penetration_falloff = weapon.penetration_falloff;
+    let crit_shot = weapon.chargeable && charge_frac >= 1.0;
+
+    let (muzzle_pos, _muzzle_dir) = weapon_muzzle_tag(world, idx);
+    spawn_effect(world, EffectType::MuzzleFlash, muzzle_pos);
+    if !explosive && !beam {
+        spawn_effect(world, EffectType::ShellCasing, muzzle_pos);
+    }
 
     for _ in 0..pellets {
-        let sx = rand::gen_range(-spread, spread);
-        let sy = rand::gen_range(-spread, spread);
-
-        let forward = world.player.forward();
-        let right = world.player.right();
+        // Each pellet/shot gets its own seeded generator derived from the
+        // master seeder, so a fixed initial seed replays identically.
+        let mut pellet_rng = Xoroshiro32PlusPlus::new(world.seeder.next_u32());
+        let sx = pellet_rng.next_f32_range(-spread, spread);
+        let sy = pellet_rng.next_f32_range(-spread, spread);
+
+        let player = world.player_ref(idx);
+        let forward = player.forward();
+        let right = player.right();
         let up = vec3(0.0, 1.0, 0.0);
 
         let direction = (forward + right * sx + up * sy).normalize();
 
         if explosive {
-            world.projectiles.push(Projectile {
-                pos: world.player.pos,
-                vel: direction * 25.0,
+            world.projectiles.spawn(Projectile {
+                pos: muzzle_pos,
+                prev_pos: muzzle_pos,
+                vel: direction * 25.0 * (0.4 + charge_frac * 0.6),
                 damage,
                 explosive: true,
+                owner: idx,
+                rng: pellet_rng,
+                life: PROJECTILE_MAX_LIFE,
+                max_life: PROJECTILE_MAX_LIFE,
             });
         } else {
-            raycast_shot(world, direction, damage);
+            let end = raycast_shot(world, idx, muzzle_pos, direction, damage, penetration, penetration_falloff, crit_shot);
+            if beam {
+                world.beams.push(Beam { start: muzzle_pos, end, birth_time: get_time(), color: beam_color });
+            }
+        }
+    }
+}
+
+/// Tints the railgun's beam hotter the more charge was behind the shot -
+/// a cool blue-white at minimum charge, climbing to white-hot at full charge.
+fn railgun_beam_color(charge_frac: f32) -> Color {
+    let t = charge_frac.clamp(0.0, 1.0);
+    Color::new(0.4 + t * 0.6, 0.6 + t * 0.4, 1.0, 1.0)
+}
+
+/// A region of an enemy's body, matching the limbs `draw_3d_clarkson`
+/// draws, each with its own damage multiplier.
+#[derive(Clone, Copy)]
+enum HitZone {
+    Head,
+    Torso,
+    Legs,
+}
+
+impl HitZone {
+    fn multiplier(self) -> f32 {
+        match self {
+            HitZone::Head => 2.5,
+            HitZone::Torso => 1.0,
+            HitZone::Legs => 0.6,
+        }
+    }
+    fn effect(self) -> EffectType {
+        match self {
+            HitZone::Head => EffectType::Headshot,
+            HitZone::Torso => EffectType::BloodSpray,
+            HitZone::Legs => EffectType::LimbHit,
+        }
+    }
+}
+
+/// Finds which body zone (if any) of `enemy` contains `pos`, checked in
+/// head -> torso -> legs priority order so overlapping spheres resolve to
+/// the more generous hit. Mirrors the proportions `draw_3d_clarkson` uses.
+fn enemy_hit_zone(enemy: &Enemy, pos: Vec3) -> Option<HitZone> {
+    let height = enemy.size() * 2.5;
+    let s = height / 4.5;
+    // Same feet position `draw_3d_clarkson` builds the limbs up from.
+    let base = enemy.pos - vec3(0.0, height * 0.4, 0.0);
+    let zones = [
+        (HitZone::Head, base + vec3(0.0, s * 2.6, 0.0), s * 0.4),
+        (HitZone::Torso, base + vec3(0.0, s * 1.6, 0.0), s * 0.7),
+        (HitZone::Legs, base + vec3(0.0, s * 0.6, 0.0), s * 0.5),
+    ];
+    for (zone, center, radius) in zones {
+        if (pos - center).length_squared() < radius * radius {
+            return Some(zone);
         }
     }
+    None
 }
 
-fn raycast_shot(world: &mut World, dir: Vec3, damage: f32) {
-    let start = world.player.pos;
+/// Marches a single ray out from `shooter`, collecting up to `penetration`
+/// distinct enemy hits (each pierced target's damage multiplied by
+/// `penetration_falloff` relative to the last) before a wall or the penetration budget
+/// stops it. Mirrors the old single-hit `raycast_shot`'s march, just kept
+/// going instead of `break`ing on the first enemy.
+/// Returns the world-space point the ray stopped at (a wall, its last
+/// pierced enemy, or its max range) - used to draw a beam along a hitscan
+/// shot, e.g. the railgun's.
+/// `crit_shot` marks a fully-charged shot, which (like a headshot) gets the
+/// larger, brighter floating damage number.
+fn raycast_shot(world: &mut World, shooter: usize, origin: Vec3, dir: Vec3, damage: f32, penetration: u32, penetration_falloff: f32, crit_shot: bool) -> Vec3 {
+    let start = origin;
     let step = 0.3;
     let max_dist = 100.0;
     let mut dist = 0.0;
 
-    // Track what particles to spawn after we're done with borrows
-    let mut particle_spawns: Vec<(Vec3, Color, i32, f32, f32)> = Vec::new();
-    let mut hit_enemy_idx: Option<usize> = None;
-    let mut hit_pos = Vec3::ZERO;
+    // Collect all hits first, to respect the borrow checker, then apply
+    // damage/combo/particles in a second pass.
+    let mut hits: Vec<(usize, HitZone, Vec3, f32)> = Vec::new();
+    let mut pierced: Vec<usize> = Vec::new();
+    let mut wall_hit: Option<Vec3> = None;
 
     while dist < max_dist {
         let pos = start + dir * dist;
 
         if world.level.check_collision(pos.x, pos.z, 0.1) {
-            particle_spawns.push((pos, GRAY, 8, 3.0, 0.1));
+            wall_hit = Some(pos);
             break;
         }
 
         for (i, enemy) in world.enemies.iter().enumerate() {
-            if enemy.dead { continue; }
-            let d = pos - enemy.pos;
-            let dist_sq = d.length_squared();
-            let hit_r = enemy.size() * 0.8;
-
-            if dist_sq < hit_r * hit_r {
-                hit_enemy_idx = Some(i);
-                hit_pos = pos;
+            if enemy.dead || pierced.contains(&i) { continue; }
+            if let Some(zone) = enemy_hit_zone(enemy, pos) {
+                let falloff_mult = penetration_falloff.powi(pierced.len() as i32);
+                hits.push((i, zone, pos, damage * falloff_mult));
+                pierced.push(i);
                 break;
             }
         }
 
-        if hit_enemy_idx.is_some() { break; }
+        if pierced.len() as u32 >= penetration { break; }
         dist += step;
     }
 
+    let end_pos = wall_hit.or_else(|| hits.last().map(|(_, _, pos, _)| *pos)).unwrap_or(start + dir * dist);
+
     // Now apply damage and spawn particles
-    if let Some(idx) = hit_enemy_idx {
+    let mut effect_spawns: Vec<(EffectType, Vec3)> = Vec::new();
+    if let Some(pos) = wall_hit {
+        effect_spawns.push((EffectType::BulletImpact, pos));
+    }
+
+    let mut floating_spawns: Vec<(Vec3, f32, bool)> = Vec::new();
+    for (idx, zone, hit_pos, hit_damage) in hits {
         let enemy = &mut world.enemies[idx];
-        let color = enemy.color();
         let enemy_pos = enemy.pos;
         let points = enemy.points();
 
-        enemy.health -= damage;
+        let actual_damage = hit_damage * zone.multiplier();
+        enemy.health -= actual_damage;
         world.hit_marker = 1.0;
-        particle_spawns.push((hit_pos, color, 10, 4.0, 0.15));
+        effect_spawns.push((zone.effect(), hit_pos));
+        floating_spawns.push((hit_pos, actual_damage, crit_shot || matches!(zone, HitZone::Head)));
 
         if enemy.health <= 0.0 {
             enemy.dead = true;
             enemy.death_time = get_time();
+            enemy.anim_state = AnimState::Dying;
 
             // Combo system!
             world.combo += 1;
             world.combo_timer = 2.0; // 2 second combo window
+            let headshot_bonus = if matches!(zone, HitZone::Head) { 100 } else { 0 };
             let combo_bonus = world.combo * 50;
-            world.player.score += points + combo_bonus;
-            world.player.kills += 1;
+            let shooter_player = world.player_mut(shooter);
+            shooter_player.score += points + combo_bonus + headshot_bonus;
+            shooter_player.kills += 1;
             world.total_kills += 1;
+            world.drop_loot(enemy_pos, points);
 
-            particle_spawns.push((enemy_pos, color, 25, 6.0, 0.2));
+            effect_spawns.push((EffectType::BloodSpray, enemy_pos));
+        } else {
+            enemy.set_anim(AnimState::Hurt, 0.25);
         }
     }
 
-    // Spawn all particles
-    for (pos, color, count, speed, size) in particle_spawns {
-        spawn_particles(world, pos, color, count, speed, size);
+    // Spawn all effects
+    for (kind, pos) in effect_spawns {
+        spawn_effect(world, kind, pos);
     }
+
+    for (pos, dmg, crit) in floating_spawns {
+        let color = if crit { GOLD } else { WHITE };
+        spawn_floating_text(world, pos + vec3(0.0, 0.3, 0.0), format!("{}", dmg.round() as i32), color, crit);
+    }
+
+    end_pos
 }
 
 fn update_enemies(world: &mut World, dt: f32, time: f64) {
-    let player_pos = vec3(world.player.pos.x, 0.0, world.player.pos.z);
-
     for i in 0..world.enemies.len() {
         if world.enemies[i].dead { continue; }
+        world.enemies[i].prev_pos = world.enemies[i].pos;
+        world.enemies[i].tick_anim(dt);
 
         let enemy_pos = vec3(world.enemies[i].pos.x, 0.0, world.enemies[i].pos.z);
-        let to_player = player_pos - enemy_pos;
+        // Each enemy chases whichever living player is nearest to it.
+        let Some(target) = world.nearest_player(enemy_pos) else { continue };
+        let target_pos = vec3(world.player_ref(target).pos.x, 0.0, world.player_ref(target).pos.z);
+        let to_player = target_pos - enemy_pos;
         let dist = to_player.length();
 
         if dist > 1.5 {
@@ -969,119 +2355,92 @@ fn update_enemies(world: &mut World, dt: f32, time: f64) {
 
         if dist < 2.0 && time - last_attack > attack_cd as f64 {
             world.enemies[i].last_attack = time;
+            world.enemies[i].set_anim(AnimState::Attacking, 0.35);
 
             // Armor absorbs damage first
             let mut actual_damage = damage;
-            if world.player.armor > 0.0 {
-                let armor_absorb = actual_damage.min(world.player.armor);
-                world.player.armor -= armor_absorb;
+            let victim = world.player_mut(target);
+            if victim.armor > 0.0 {
+                let armor_absorb = actual_damage.min(victim.armor);
+                victim.armor -= armor_absorb;
                 actual_damage -= armor_absorb * 0.7; // Armor is 70% effective
             }
 
-            world.player.health = (world.player.health - actual_damage).max(0.0);
-            world.player.damage_flash = 0.5;
+            let victim = world.player_mut(target);
+            if !victim.invulnerable {
+                victim.health = (victim.health - actual_damage).max(0.0);
+            }
+            victim.damage_flash = 0.5;
             world.screen_shake = 0.25;
         }
     }
 }
 
 fn update_projectiles(world: &mut World, dt: f32) {
-    let mut explosions: Vec<(Vec3, f32)> = Vec::new();
-
-    world.projectiles.retain_mut(|proj| {
-        proj.pos += proj.vel * dt;
-
-        if world.level.check_collision(proj.pos.x, proj.pos.z, 0.2) {
-            if proj.explosive { explosions.push((proj.pos, proj.damage)); }
-            return false;
-        }
-
-        for enemy in &mut world.enemies {
-            if enemy.dead { continue; }
-            if (proj.pos - enemy.pos).length() < 1.0 {
-                if proj.explosive { explosions.push((proj.pos, proj.damage)); }
-                else { enemy.health -= proj.damage; }
-                return false;
-            }
-        }
-        true
-    });
+    let explosions = world.projectiles.tick(dt, &world.level, &mut world.enemies);
 
-    for (pos, damage) in explosions {
-        explode(world, pos, damage);
+    for (pos, damage, owner) in explosions {
+        explode(world, pos, damage, owner);
     }
 }
 
-fn explode(world: &mut World, pos: Vec3, damage: f32) {
+fn explode(world: &mut World, pos: Vec3, damage: f32, owner: usize) {
     let radius = 10.0; // Massive blast radius
     world.screen_shake = 0.8; // Big screen shake
 
-    // MASSIVE explosion particles - fireballs
-    for _ in 0..120 {
-        let vel = vec3(
-            rand::gen_range(-15.0, 15.0),
-            rand::gen_range(3.0, 20.0),
-            rand::gen_range(-15.0, 15.0),
-        );
-        let colors = [ORANGE, YELLOW, RED, Color::new(1.0, 0.5, 0.0, 1.0)];
-        let color = colors[rand::gen_range(0, 4)];
-        world.particles.push(Particle {
-            pos, vel, color, life: rand::gen_range(0.5, 1.8), max_life: 1.8, size: rand::gen_range(0.3, 0.8)
-        });
-    }
-
-    // Smoke particles (slower, darker, longer lasting)
-    for _ in 0..40 {
-        let vel = vec3(
-            rand::gen_range(-5.0, 5.0),
-            rand::gen_range(1.0, 8.0),
-            rand::gen_range(-5.0, 5.0),
-        );
-        world.particles.push(Particle {
-            pos, vel, color: Color::new(0.3, 0.3, 0.3, 0.8), life: rand::gen_range(1.0, 2.5), max_life: 2.5, size: rand::gen_range(0.4, 1.0)
-        });
-    }
-
-    // Bright white-hot core flash particles
-    for _ in 0..20 {
-        let vel = vec3(
-            rand::gen_range(-20.0, 20.0),
-            rand::gen_range(5.0, 25.0),
-            rand::gen_range(-20.0, 20.0),
-        );
-        world.particles.push(Particle {
-            pos, vel, color: WHITE, life: rand::gen_range(0.1, 0.4), max_life: 0.4, size: rand::gen_range(0.5, 1.2)
-        });
-    }
+    spawn_effect(world, EffectType::Explosion, pos);
 
-    // Damage enemies
+    // Damage enemies, crediting kills to whoever fired the projectile
+    let mut points_earned = 0;
+    let mut kills: Vec<(Vec3, i32)> = Vec::new();
+    let mut floating_spawns: Vec<(Vec3, f32)> = Vec::new();
     for enemy in &mut world.enemies {
         if enemy.dead { continue; }
         let dist = (pos - enemy.pos).length();
         if dist < radius {
             let falloff = 1.0 - dist / radius;
-            enemy.health -= damage * falloff;
+            let actual_damage = damage * falloff;
+            enemy.health -= actual_damage;
+            floating_spawns.push((enemy.pos, actual_damage));
             if enemy.health <= 0.0 {
                 enemy.dead = true;
                 enemy.death_time = get_time();
-                world.player.score += enemy.points();
+                enemy.anim_state = AnimState::Dying;
+                points_earned += enemy.points();
+                kills.push((enemy.pos, enemy.points()));
             }
         }
     }
+    if points_earned > 0 {
+        world.player_mut(owner).score += points_earned;
+    }
+    for (kill_pos, points) in kills {
+        world.drop_loot(kill_pos, points);
+    }
+    for (enemy_pos, dmg) in floating_spawns {
+        spawn_floating_text(world, enemy_pos + vec3(0.0, 0.3, 0.0), format!("{}", dmg.round() as i32), WHITE, false);
+    }
 
-    // Self damage
-    let player_dist = (pos - world.player.pos).length();
-    if player_dist < radius {
-        let falloff = 1.0 - player_dist / radius;
-        world.player.health = (world.player.health - damage * falloff * 0.3).max(0.0);
-        world.player.damage_flash = 0.3;
+    // Blast only hurts the player(s) caught in its radius - in co-op, a
+    // rocket can catch the thrower's teammate in the splash too.
+    for i in world.player_indices() {
+        let player_dist = (pos - world.player_ref(i).pos).length();
+        if player_dist < radius {
+            let falloff = 1.0 - player_dist / radius;
+            let p = world.player_mut(i);
+            if !p.invulnerable {
+                p.health = (p.health - damage * falloff * 0.3).max(0.0);
+            }
+            p.damage_flash = 0.3;
+        }
     }
 }
 
 fn update_particles(world: &mut World, dt: f32) {
     for p in &mut world.particles {
+        p.prev_pos = p.pos;
         p.pos += p.vel * dt;
-        p.vel.y -= 15.0 * dt;
+        p.vel.y -= 15.0 * dt * p.gravity_scale;
         p.life -= dt;
     }
     world.particles.retain(|p| p.life > 0.0);
@@ -1095,75 +2454,244 @@ fn spawn_particles(world: &mut World, pos: Vec3, color: Color, count: i32, speed
             rand::gen_range(-speed, speed),
         );
         world.particles.push(Particle {
-            pos, vel, color, life: rand::gen_range(0.2, 0.5), max_life: 0.5, size
+            pos, prev_pos: pos, vel, color, life: rand::gen_range(0.2, 0.5), max_life: 0.5, size, gravity_scale: 1.0
         });
     }
 }
 
-fn update_pickups(world: &mut World, dt: f32) {
-    // Decay pickup message
-    world.player.pickup_msg_time -= dt;
+/// Accumulators driving the fixed-frequency emitter tiers below - each
+/// fires "1.0 / hz" seconds apart regardless of how often `update_emitters`
+/// itself gets called, so particle density is the same at 30 FPS or 300.
+struct EmitterTimers {
+    hz5: f32,
+    hz10: f32,
+    hz50: f32,
+    hz100: f32,
+}
 
-    // Decay powerups
-    world.player.speed_boost = (world.player.speed_boost - dt).max(0.0);
-    world.player.damage_boost = (world.player.damage_boost - dt).max(0.0);
+impl EmitterTimers {
+    fn new() -> Self {
+        Self { hz5: 0.0, hz10: 0.0, hz50: 0.0, hz100: 0.0 }
+    }
+}
 
-    // Collect particle spawns to avoid borrow issues
-    let mut particle_spawns: Vec<(Vec3, Color)> = Vec::new();
+/// Ticks every emitter tier and fires each tier's subscribed emitter(s)
+/// however many times `dt` demands, decoupling "how often we emit" from
+/// "how often we render." Only the 5 Hz (`AmbientFlakes`) and 50 Hz
+/// (`PowerupShine`) tiers have subscribers today; 10 Hz/100 Hz tick for
+/// future emitters to plug into.
+fn update_emitters(world: &mut World, dt: f32) {
+    world.emitter_timers.hz5 += dt;
+    world.emitter_timers.hz10 += dt;
+    world.emitter_timers.hz50 += dt;
+    world.emitter_timers.hz100 += dt;
+
+    while world.emitter_timers.hz5 >= 1.0 / 5.0 {
+        world.emitter_timers.hz5 -= 1.0 / 5.0;
+        emit_ambient_flakes(world);
+    }
+    while world.emitter_timers.hz10 >= 1.0 / 10.0 {
+        world.emitter_timers.hz10 -= 1.0 / 10.0;
+    }
+    while world.emitter_timers.hz50 >= 1.0 / 50.0 {
+        world.emitter_timers.hz50 -= 1.0 / 50.0;
+        emit_powerup_shine(world);
+    }
+    while world.emitter_timers.hz100 >= 1.0 / 100.0 {
+        world.emitter_timers.hz100 -= 1.0 / 100.0;
+    }
+}
 
-    // Check pickup collection
-    for pickup in &mut world.pickups {
+/// 50 Hz emitter: sprinkles a short-lived spark particle jittered inside
+/// every uncollected pickup's bounding box, layered onto its existing
+/// bob/tumble render for a bit of shine.
+fn emit_powerup_shine(world: &mut World) {
+    for i in 0..world.pickups.len() {
+        let pickup = &world.pickups[i];
         if pickup.collected { continue; }
+        let color = pickup.color();
+        let jitter = vec3(
+            rand::gen_range(-0.25, 0.25),
+            rand::gen_range(-0.25, 0.25),
+            rand::gen_range(-0.25, 0.25),
+        );
+        let pos = pickup.pos + vec3(0.0, 0.5, 0.0) + jitter;
+        world.particles.push(Particle {
+            pos,
+            prev_pos: pos,
+            vel: vec3(rand::gen_range(-0.3, 0.3), rand::gen_range(0.2, 0.6), rand::gen_range(-0.3, 0.3)),
+            color,
+            life: 0.25,
+            max_life: 0.25,
+            size: 0.05,
+            gravity_scale: 0.2,
+        });
+    }
+}
+
+/// 5 Hz emitter: drifts a couple of slow dust-mote particles into the air
+/// around each active player for atmosphere, independent of whatever
+/// combat particles are flying at the time.
+fn emit_ambient_flakes(world: &mut World) {
+    for i in world.player_indices() {
+        let origin = world.player_ref(i).pos;
+        for _ in 0..2 {
+            let pos = origin + vec3(
+                rand::gen_range(-6.0, 6.0),
+                rand::gen_range(0.5, 3.5),
+                rand::gen_range(-6.0, 6.0),
+            );
+            world.particles.push(Particle {
+                pos,
+                prev_pos: pos,
+                vel: vec3(rand::gen_range(-0.1, 0.1), -0.05, rand::gen_range(-0.1, 0.1)),
+                color: Color::new(0.8, 0.8, 0.75, 0.5),
+                life: 3.0,
+                max_life: 3.0,
+                size: 0.03,
+                gravity_scale: 0.05,
+            });
+        }
+    }
+}
+
+/// Single entry point for requesting a visual effect: weapon fire, hits,
+/// detonations and pickups all call this instead of poking scalar timers or
+/// hand-rolled particle loops directly.
+fn spawn_effect(world: &mut World, kind: EffectType, pos: Vec3) {
+    world.effects.push(Effect { kind, pos, life: kind.max_life(), max_life: kind.max_life() });
+    spawn_particles(world, pos, kind.color(), kind.particle_count(), kind.particle_speed(), kind.billboard_size() * 0.5);
+}
+
+fn update_effects(world: &mut World, dt: f32) {
+    for e in &mut world.effects {
+        e.life -= dt;
+    }
+    world.effects.retain(|e| e.life > 0.0);
+}
+
+fn update_beams(world: &mut World) {
+    let now = get_time();
+    world.beams.retain(|b| (now - b.birth_time) < BEAM_LIFETIME as f64);
+}
+
+/// Pushes a rising, fading damage number at `pos`. `crit` flags a headshot
+/// or a fully-charged shot for the larger, brighter render style.
+fn spawn_floating_text(world: &mut World, pos: Vec3, text: String, color: Color, crit: bool) {
+    world.floating_texts.push(FloatingText { world_pos: pos, text, birth_time: get_time(), color, crit });
+}
+
+fn update_floating_texts(world: &mut World) {
+    let now = get_time();
+    world.floating_texts.retain(|f| (now - f.birth_time) < FLOATING_TEXT_LIFETIME as f64);
+}
 
-        let dx = world.player.pos.x - pickup.pos.x;
-        let dz = world.player.pos.z - pickup.pos.z;
-        let dist = (dx * dx + dz * dz).sqrt();
+fn update_pickups(world: &mut World, dt: f32) {
+    // Decay pickup message and powerups for every active player
+    for i in world.player_indices() {
+        let p = world.player_mut(i);
+        p.pickup_msg_time -= dt;
+        p.speed_boost = (p.speed_boost - dt).max(0.0);
+        p.damage_boost = (p.damage_boost - dt).max(0.0);
+    }
+
+    // Physics step for dropped loot still in flight: integrate, apply the
+    // same gravity `update_particles` uses, and bounce off the floor with
+    // damping until it's slow enough to call settled.
+    for pickup in &mut world.pickups {
+        if pickup.settled || pickup.collected { continue; }
+        pickup.pos += pickup.vel * dt;
+        pickup.vel.y -= 15.0 * dt;
+        pickup.spin += (pickup.vel.x.abs() + pickup.vel.z.abs()) * dt;
+
+        let floor = world.level.get_floor_height(pickup.pos.x, pickup.pos.z) + 0.5;
+        if pickup.pos.y <= floor {
+            pickup.pos.y = floor;
+            if pickup.vel.y < 0.0 {
+                pickup.vel.y *= -0.45; // Bounce with damping
+                pickup.vel.x *= 0.7;
+                pickup.vel.z *= 0.7;
+            }
+            if pickup.vel.length() < 0.6 {
+                pickup.vel = Vec3::ZERO;
+                pickup.settled = true;
+            }
+        }
+    }
 
-        if dist < 1.5 {
-            pickup.collected = true;
-            world.player.pickup_msg = format!("+{}", pickup.name());
-            world.player.pickup_msg_time = 2.0;
+    // Collect effect spawns to avoid borrow issues
+    let mut effect_spawns: Vec<Vec3> = Vec::new();
 
-            // Store particle data for later
-            particle_spawns.push((pickup.pos, pickup.color()));
+    // Check pickup collection - first active player in range claims it
+    for idx in 0..world.pickups.len() {
+        if world.pickups[idx].collected { continue; }
+        let pickup_pos = world.pickups[idx].pos;
 
-            match pickup.pickup_type {
+        let collector = world.player_indices().find(|&i| {
+            let p = world.player_ref(i);
+            let dx = p.pos.x - pickup_pos.x;
+            let dz = p.pos.z - pickup_pos.z;
+            (dx * dx + dz * dz).sqrt() < 1.5
+        });
+
+        if let Some(i) = collector {
+            world.pickups[idx].collected = true;
+            effect_spawns.push(pickup_pos);
+            let pickup_name = world.pickups[idx].name().to_string();
+            let pickup_type = world.pickups[idx].pickup_type;
+
+            let p = world.player_mut(i);
+            p.pickup_msg = format!("+{}", pickup_name);
+            p.pickup_msg_time = 2.0;
+
+            match pickup_type {
                 PickupType::Health => {
-                    world.player.health = (world.player.health + 25.0).min(MAX_HEALTH);
+                    p.health = (p.health + 25.0).min(MAX_HEALTH);
                 }
                 PickupType::Ammo => {
-                    for weapon in &mut world.player.weapons {
-                        if weapon.max_ammo > 0 {  // Only refill weapons with limited ammo
-                            weapon.ammo = (weapon.ammo + weapon.max_ammo / 2).min(weapon.max_ammo);
+                    // Tops up the reserve of weapons the player already
+                    // owns - it can't grant ammo for a gun they don't have.
+                    for weapon in &mut p.weapons {
+                        if weapon.owned && weapon.max_reserve > 0 {
+                            weapon.reserve = (weapon.reserve + weapon.max_reserve / 2).min(weapon.max_reserve);
                         }
                     }
                 }
                 PickupType::SpeedBoost => {
-                    world.player.speed_boost = 10.0; // 10 second speed boost
+                    p.speed_boost = 10.0; // 10 second speed boost
                 }
                 PickupType::DamageBoost => {
-                    world.player.damage_boost = 10.0; // 10 second damage boost
+                    p.damage_boost = 10.0; // 10 second damage boost
                 }
                 PickupType::Armor => {
-                    world.player.armor = (world.player.armor + 50.0).min(100.0);
+                    p.armor = (p.armor + 50.0).min(100.0);
+                }
+                PickupType::WeaponUnlock => {
+                    if let Some(weapon) = p.weapons.iter_mut().find(|w| !w.owned) {
+                        weapon.owned = true;
+                    }
                 }
             }
 
-            world.player.score += 50;
+            p.score += 50;
         }
     }
 
-    // Spawn particles after the loop
-    for (pos, color) in particle_spawns {
-        spawn_particles(world, pos, color, 15, 5.0, 0.15);
+    // Spawn effects after the loop
+    for pos in effect_spawns {
+        spawn_effect(world, EffectType::PickupSparkle, pos);
     }
 }
 
 fn check_victory(world: &mut World) {
     if world.alive_enemies() == 0 {
         let (ex, ez) = World::find_char(&world.level, 'X');
-        let dist = ((world.player.pos.x - ex).powi(2) + (world.player.pos.z - ez).powi(2)).sqrt();
-        if dist < 2.0 {
+        // Either living player reaching the exit clears the level for the team.
+        let someone_at_exit = world.player_indices().any(|i| {
+            let p = world.player_ref(i);
+            p.alive() && ((p.pos.x - ex).powi(2) + (p.pos.z - ez).powi(2)).sqrt() < 2.0
+        });
+        if someone_at_exit {
             world.state = GameState::Victory;
             set_cursor_grab(false);
             show_mouse(true);
@@ -1175,53 +2703,18 @@ fn check_victory(world: &mut World) {
 // RENDERING
 // ============================================================================
 
-fn draw_billboard_sprite(texture: &Texture2D, pos: Vec3, size: f32, player: &Player, tint: Color) {
-    // Calculate billboard orientation (always face the player)
-    let to_player = vec3(player.pos.x - pos.x, 0.0, player.pos.z - pos.z).normalize();
-    let right = vec3(-to_player.z, 0.0, to_player.x);
-    let up = vec3(0.0, 1.0, 0.0);
-
-    let half_size = size / 2.0;
-    let color_bytes: [u8; 4] = [
-        (tint.r * 255.0) as u8,
-        (tint.g * 255.0) as u8,
-        (tint.b * 255.0) as u8,
-        (tint.a * 255.0) as u8,
-    ];
-
-    // Four corners of the billboard
-    let v1 = pos + right * (-half_size) + up * size;  // top-left
-    let v2 = pos + right * half_size + up * size;     // top-right
-    let v3 = pos + right * half_size;                  // bottom-right
-    let v4 = pos + right * (-half_size);               // bottom-left
-
-    let normal = vec4(0.0, 0.0, 1.0, 0.0);
-
-    // Draw textured quad using mesh for proper 3D billboard
-    let vertices: [Vertex; 4] = [
-        Vertex { position: v1, uv: vec2(0.0, 0.0), color: color_bytes, normal },
-        Vertex { position: v2, uv: vec2(1.0, 0.0), color: color_bytes, normal },
-        Vertex { position: v3, uv: vec2(1.0, 1.0), color: color_bytes, normal },
-        Vertex { position: v4, uv: vec2(0.0, 1.0), color: color_bytes, normal },
-    ];
-    let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
-
-    draw_mesh(&Mesh {
-        vertices: vertices.to_vec(),
-        indices: indices.to_vec(),
-        texture: Some(texture.clone()),
-    });
-}
-
 // Draw a REAL 3D Clarkson model using geometric primitives (humanoid figure)
-fn draw_3d_clarkson(_texture: &Texture2D, pos: Vec3, size: f32, tint: Color, player: &Player, time: f32) {
+#[allow(clippy::too_many_arguments)]
+fn draw_3d_clarkson(_texture: &Texture2D, pos: Vec3, size: f32, tint: Color, player: &Player, time: f32,
+                     anim_state: AnimState, anim_transition: f32, death_progress: f32) {
     // Face the player (rotate towards them)
     let to_player = vec3(player.pos.x - pos.x, 0.0, player.pos.z - pos.z);
     let angle = to_player.z.atan2(to_player.x);
 
-    // Walking animation
-    let walk_cycle = (time * 8.0).sin() * 0.15;
-    let arm_swing = (time * 8.0).sin() * 0.2;
+    // Walking animation - stilled once a hit/lunge pose takes over
+    let walk_damp = 1.0 - anim_transition;
+    let walk_cycle = (time * 8.0).sin() * 0.15 * walk_damp;
+    let arm_swing = (time * 8.0).sin() * 0.2 * walk_damp;
 
     // Scale - size is the full height we want
     let height = size;
@@ -1255,29 +2748,50 @@ fn draw_3d_clarkson(_texture: &Texture2D, pos: Vec3, size: f32, tint: Color, pla
         )
     };
 
+    // How far (forward/back) the lunge/flinch pushes limbs, scaled by how
+    // deep into that pose we've blended.
+    let lunge = if anim_state == AnimState::Attacking { anim_transition } else { 0.0 };
+    let flinch = if anim_state == AnimState::Hurt { anim_transition } else { 0.0 };
+    let topple = death_progress.clamp(0.0, 1.0) * (PI / 2.0);
+    let (topple_cos, topple_sin) = (topple.cos(), topple.sin());
+
+    // Places a body part given its forward/lateral offset from `base` and
+    // how high above `base` it normally sits, blending in the death
+    // topple: the whole figure pitches backward (away from the player) and
+    // down over `death_time`
+    // rather than just popping to a ground pose.
+    let place = |local_forward: f32, height_above_base: f32, local_lateral: f32| -> Vec3 {
+        let forward = local_forward * topple_cos - height_above_base * topple_sin;
+        let up = local_forward * topple_sin + height_above_base * topple_cos;
+        base + rotate(vec3(forward, 0.0, local_lateral)) + vec3(0.0, up, 0.0)
+    };
+
     // FEET (at ground level) - Z is left/right, X is forward/back for walking
-    let foot_y = base.y + s * 0.1;
-    let left_foot_offset = rotate(vec3(walk_cycle * s, 0.0, -s * 0.2));
-    let right_foot_offset = rotate(vec3(-walk_cycle * s, 0.0, s * 0.2));
-    draw_cube(base + left_foot_offset + vec3(0.0, foot_y - base.y, 0.0), vec3(s * 0.35, s * 0.15, s * 0.25), None, shoe_color);
-    draw_cube(base + right_foot_offset + vec3(0.0, foot_y - base.y, 0.0), vec3(s * 0.35, s * 0.15, s * 0.25), None, shoe_color);
+    let foot_y = s * 0.1;
+    let left_foot_pos = place(walk_cycle * s, foot_y, -s * 0.2);
+    let right_foot_pos = place(-walk_cycle * s, foot_y, s * 0.2);
+    draw_cube(left_foot_pos, vec3(s * 0.35, s * 0.15, s * 0.25), None, shoe_color);
+    draw_cube(right_foot_pos, vec3(s * 0.35, s * 0.15, s * 0.25), None, shoe_color);
 
     // LEGS
-    let leg_y = base.y + s * 0.6;
-    draw_cube(base + left_foot_offset + vec3(0.0, leg_y - base.y, 0.0), vec3(s * 0.22, s * 0.8, s * 0.22), None, pants_color);
-    draw_cube(base + right_foot_offset + vec3(0.0, leg_y - base.y, 0.0), vec3(s * 0.22, s * 0.8, s * 0.22), None, pants_color);
+    let leg_y = s * 0.6;
+    draw_cube(place(walk_cycle * s, leg_y, -s * 0.2), vec3(s * 0.22, s * 0.8, s * 0.22), None, pants_color);
+    draw_cube(place(-walk_cycle * s, leg_y, s * 0.2), vec3(s * 0.22, s * 0.8, s * 0.22), None, pants_color);
 
-    // TORSO (wider on Z = left/right, thinner on X = front/back)
-    let torso_y = base.y + s * 1.6;
-    draw_cube(base + vec3(0.0, torso_y - base.y, 0.0), vec3(s * 0.35, s * 1.0, s * 0.6), None, shirt_color);
+    // TORSO (wider on Z = left/right, thinner on X = front/back) - lunges
+    // forward on an attack, rocks back on a flinch.
+    let torso_y = s * 1.6;
+    let torso_forward = lunge * s * 0.5 - flinch * s * 0.35;
+    draw_cube(place(torso_forward, torso_y, 0.0), vec3(s * 0.35, s * 1.0, s * 0.6), None, shirt_color);
 
     // NECK
-    let neck_y = base.y + s * 2.2;
-    draw_cube(base + vec3(0.0, neck_y - base.y, 0.0), vec3(s * 0.2, s * 0.15, s * 0.2), None, skin_color);
+    let neck_y = s * 2.2;
+    draw_cube(place(torso_forward, neck_y, 0.0), vec3(s * 0.2, s * 0.15, s * 0.2), None, skin_color);
 
-    // HEAD
-    let head_y = base.y + s * 2.6;
-    let head_pos = base + vec3(0.0, head_y - base.y, 0.0);
+    // HEAD - flinches back further than the torso; a lunge cranes it forward
+    let head_y = s * 2.6;
+    let head_forward = torso_forward + lunge * s * 0.15 - flinch * s * 0.15;
+    let head_pos = place(head_forward, head_y, 0.0);
     draw_sphere(head_pos, s * 0.35, None, skin_color);
 
     // Hair on top
@@ -1295,17 +2809,20 @@ fn draw_3d_clarkson(_texture: &Texture2D, pos: Vec3, size: f32, tint: Color, pla
     draw_sphere(head_pos + pupil_forward + eye_left + vec3(0.0, s * 0.05, 0.0), s * 0.03, None, BLACK);
     draw_sphere(head_pos + pupil_forward + eye_right + vec3(0.0, s * 0.05, 0.0), s * 0.03, None, BLACK);
 
-    // ARMS (Z axis is left/right when X is forward)
-    let arm_y = base.y + s * 1.8;
-    let left_arm_offset = rotate(vec3(arm_swing * s * 0.5, 0.0, -s * 0.45));
-    let right_arm_offset = rotate(vec3(-arm_swing * s * 0.5, 0.0, s * 0.45));
-    draw_cube(base + left_arm_offset + vec3(0.0, arm_y - base.y, 0.0), vec3(s * 0.18, s * 0.7, s * 0.18), None, shirt_color);
-    draw_cube(base + right_arm_offset + vec3(0.0, arm_y - base.y, 0.0), vec3(s * 0.18, s * 0.7, s * 0.18), None, shirt_color);
+    // ARMS (Z axis is left/right when X is forward) - the right arm throws
+    // forward into the lunge, as if swinging at the player.
+    let arm_y = s * 1.8;
+    let left_arm_forward = arm_swing * s * 0.5 - flinch * s * 0.3;
+    let right_arm_forward = -arm_swing * s * 0.5 + lunge * s * 0.9 - flinch * s * 0.3;
+    let left_arm_pos = place(left_arm_forward, arm_y, -s * 0.45);
+    let right_arm_pos = place(right_arm_forward, arm_y, s * 0.45);
+    draw_cube(left_arm_pos, vec3(s * 0.18, s * 0.7, s * 0.18), None, shirt_color);
+    draw_cube(right_arm_pos, vec3(s * 0.18, s * 0.7, s * 0.18), None, shirt_color);
 
     // Hands
-    let hand_y = base.y + s * 1.35;
-    draw_sphere(base + left_arm_offset + vec3(0.0, hand_y - base.y, 0.0), s * 0.1, None, skin_color);
-    draw_sphere(base + right_arm_offset + vec3(0.0, hand_y - base.y, 0.0), s * 0.1, None, skin_color);
+    let hand_y = s * 1.35;
+    draw_sphere(place(left_arm_forward, hand_y, -s * 0.45), s * 0.1, None, skin_color);
+    draw_sphere(place(right_arm_forward, hand_y, s * 0.45), s * 0.1, None, skin_color);
 }
 
 // Generate a procedural "Jeremy Clarkson" style face texture
@@ -1406,7 +2923,38 @@ fn generate_clarkson_texture() -> Texture2D {
     texture
 }
 
-fn render_3d(world: &World) {
+/// Projects a world-space point through `cam`'s view-projection matrix into
+/// pixel coordinates within `(ox, oy, vw, vh)`, mirroring how
+/// `Camera2D::world_to_screen` maps clip space onto a viewport rect.
+/// Returns `None` when the point is behind the camera, where the mapping
+/// isn't meaningful.
+fn world_to_screen_3d(cam: &Camera3D, (ox, oy, vw, vh): (f32, f32, f32, f32), world_pos: Vec3) -> Option<Vec2> {
+    let clip = cam.matrix().mul_vec4(world_pos.extend(1.0));
+    if clip.w <= 0.01 { return None; }
+    let ndc_x = clip.x / clip.w;
+    let ndc_y = clip.y / clip.w;
+    Some(vec2(
+        ox + (ndc_x * 0.5 + 0.5) * vw,
+        oy + (1.0 - (ndc_y * 0.5 + 0.5)) * vh,
+    ))
+}
+
+/// Renders the 3D scene from one player's eyes. `viewport` is the
+/// macroquad screen rect `(x, y, w, h)` to render into - `None` for a full
+/// screen, `Some(..)` for a split-screen co-op half. `eye_offset` shifts
+/// just the camera position along the player's right vector, for
+/// `world.stereo_enabled`'s side-by-side pass - `Vec3::ZERO` for a normal
+/// single-eye view.
+fn render_3d(world: &World, idx: usize, viewport: Option<(i32, i32, i32, i32)>, eye_offset: Vec3) {
+    let viewer = world.player_ref(idx);
+
+    // How far we are between the last fixed update tick and the next one -
+    // enemies, projectiles and particles below are drawn at
+    // prev_pos.lerp(pos, interp_alpha) instead of their raw current position,
+    // so motion stays smooth even when the display refresh rate doesn't line
+    // up with FIXED_DT. Loot pickups still in flight are drawn at their raw `pos`.
+    let interp_alpha = (world.accumulator / FIXED_DT).clamp(0.0, 1.0);
+
     // Camera with shake
     let shake = if world.screen_shake > 0.0 {
         vec3(
@@ -1416,16 +2964,23 @@ fn render_3d(world: &World) {
         )
     } else { Vec3::ZERO };
 
-    let cam_pos = world.player.pos + shake;
-    let cam_target = cam_pos + world.player.forward() * 10.0;
+    // The convergence point stays fixed at the player's own forward
+    // direction regardless of eye_offset, so offsetting only cam_pos below
+    // gives the two stereo passes a slight toe-in toward the same point
+    // instead of parallel (and visually divergent) view axes.
+    let cam_pos = viewer.pos + shake + eye_offset;
+    let cam_target = viewer.pos + shake + viewer.forward() * 10.0;
 
-    // Fixed FOV - dynamic FOV causes camera inversion issues in macroquad
+    // FOV comes from Settings (Options menu) rather than animating per-shot -
+    // per-frame FOV changes from ADS zoom caused camera inversion issues in
+    // macroquad, so it's only ever nudged by the player between frames.
     set_camera(&Camera3D {
         position: cam_pos,
         target: cam_target,
         up: Vec3::Y,
-        fovy: 70.0,
+        fovy: world.settings.fov,
         projection: Projection::Perspective,
+        viewport,
         ..Default::default()
     });
 
@@ -1498,16 +3053,26 @@ fn render_3d(world: &World) {
     // Enemies
     let time = get_time();
     for enemy in &world.enemies {
+        let draw_pos = enemy.prev_pos.lerp(enemy.pos, interp_alpha);
+
         if enemy.dead {
             let progress = ((time - enemy.death_time) as f32).min(1.0);
             if progress < 1.0 {
-                let scale = 1.0 - progress;
-                let y_off = progress * enemy.size();
                 if let Some(tex) = &world.enemy_texture {
-                    draw_billboard_sprite(tex, enemy.pos - vec3(0.0, y_off, 0.0), enemy.size() * 2.0 * scale, &world.player, Color::new(1.0, 1.0, 1.0, 1.0 - progress));
+                    // Topple the full model toward the ground rather than
+                    // just shrinking a billboard.
+                    let tint = match enemy.etype {
+                        EnemyType::Grunt => WHITE,
+                        EnemyType::Heavy => Color::new(0.7, 0.5, 1.0, 1.0),
+                        EnemyType::Demon => Color::new(1.0, 0.6, 0.3, 1.0),
+                    };
+                    draw_3d_clarkson(tex, draw_pos, enemy.size() * 2.5, tint, viewer, time as f32,
+                                      AnimState::Dying, 1.0, progress);
                 } else {
+                    let scale = 1.0 - progress;
+                    let y_off = progress * enemy.size();
                     draw_cube(
-                        vec3(enemy.pos.x, enemy.pos.y - y_off, enemy.pos.z),
+                        vec3(draw_pos.x, draw_pos.y - y_off, draw_pos.z),
                         vec3(enemy.size() * scale, enemy.size() * 2.0 * scale, enemy.size() * scale),
                         None, enemy.color()
                     );
@@ -1523,47 +3088,82 @@ fn render_3d(world: &World) {
                 EnemyType::Heavy => Color::new(0.7, 0.5, 1.0, 1.0),
                 EnemyType::Demon => Color::new(1.0, 0.6, 0.3, 1.0),
             };
-            // Draw real 3D Clarkson model with walking animation
-            draw_3d_clarkson(tex, enemy.pos, enemy.size() * 2.5, tint, &world.player, time as f32);
+            // Draw real 3D Clarkson model, blended toward its current anim state
+            draw_3d_clarkson(tex, draw_pos, enemy.size() * 2.5, tint, viewer, time as f32,
+                              enemy.anim_state, enemy.anim_transition, 0.0);
         } else {
             // Fallback cube rendering
-            draw_cube(enemy.pos, vec3(enemy.size(), enemy.size() * 2.0, enemy.size()), None, enemy.color());
-            let to_player = (world.player.pos - enemy.pos).normalize();
-            let eye_pos = enemy.pos + vec3(to_player.x * enemy.size() * 0.5, enemy.size() * 0.5, to_player.z * enemy.size() * 0.5);
+            draw_cube(draw_pos, vec3(enemy.size(), enemy.size() * 2.0, enemy.size()), None, enemy.color());
+            let to_player = (viewer.pos - draw_pos).normalize();
+            let eye_pos = draw_pos + vec3(to_player.x * enemy.size() * 0.5, enemy.size() * 0.5, to_player.z * enemy.size() * 0.5);
             draw_sphere(eye_pos, 0.15, None, YELLOW);
         }
 
         // Health bar
         let hp_pct = enemy.health / enemy.max_health;
         let bar_w = enemy.size() * 1.5;
-        let bar_pos = enemy.pos + vec3(0.0, enemy.size() * 1.8, 0.0);
+        let bar_pos = draw_pos + vec3(0.0, enemy.size() * 1.8, 0.0);
         draw_cube(bar_pos, vec3(bar_w, 0.1, 0.1), None, DARKGRAY);
         draw_cube(bar_pos + vec3((hp_pct - 1.0) * bar_w * 0.5, 0.0, 0.0), vec3(bar_w * hp_pct, 0.12, 0.12), None, RED);
     }
 
     // Projectiles (rockets with trail)
-    for proj in &world.projectiles {
+    for proj in &world.projectiles.projectiles {
         if proj.explosive {
+            let draw_pos = proj.prev_pos.lerp(proj.pos, interp_alpha);
             // Rocket body
-            draw_sphere(proj.pos, 0.4, None, Color::new(0.3, 0.35, 0.3, 1.0));
+            draw_sphere(draw_pos, 0.4, None, Color::new(0.3, 0.35, 0.3, 1.0));
             // Glowing tip
-            draw_sphere(proj.pos + proj.vel.normalize() * 0.3, 0.25, None, ORANGE);
+            draw_sphere(draw_pos + proj.vel.normalize() * 0.3, 0.25, None, ORANGE);
             // Fiery trail
             for i in 1..6 {
-                let trail_pos = proj.pos - proj.vel.normalize() * (i as f32 * 0.3);
-                let alpha = 1.0 - (i as f32 / 6.0);
+                let trail_pos = draw_pos - proj.vel.normalize() * (i as f32 * 0.3);
+                let trail_alpha = 1.0 - (i as f32 / 6.0);
                 let size = 0.35 - (i as f32 * 0.04);
-                draw_sphere(trail_pos, size, None, Color::new(1.0, 0.5 * alpha, 0.0, alpha));
+                draw_sphere(trail_pos, size, None, Color::new(1.0, 0.5 * trail_alpha, 0.0, trail_alpha));
             }
         }
     }
 
     // Particles
     for p in &world.particles {
-        let alpha = p.life / p.max_life;
+        let draw_pos = p.prev_pos.lerp(p.pos, interp_alpha);
+        let fade = p.life / p.max_life;
         let mut c = p.color;
+        c.a = fade;
+        draw_sphere(draw_pos, p.size, None, c);
+    }
+
+    // Typed effects (muzzle flashes, impacts, explosions, sparkle, ...)
+    for e in &world.effects {
+        let progress = 1.0 - e.life / e.max_life;
+        let alpha = 1.0 - progress;
+        let size = e.kind.billboard_size() * (0.3 + progress * 0.7);
+        let mut c = e.kind.color();
         c.a = alpha;
-        draw_sphere(p.pos, p.size, None, c);
+        draw_sphere(e.pos, size, None, c);
+    }
+
+    // Railgun beams - a stack of short segments fading out over BEAM_LIFETIME
+    let now = get_time();
+    for beam in &world.beams {
+        let age = (now - beam.birth_time) as f32;
+        let alpha = (1.0 - age / BEAM_LIFETIME).max(0.0);
+        if alpha <= 0.0 { continue; }
+
+        let delta = beam.end - beam.start;
+        let len = delta.length();
+        if len <= 0.001 { continue; }
+        let dir = delta / len;
+        let segments = (len / 0.5).ceil().max(1.0) as i32;
+
+        for i in 0..segments {
+            let seg_pos = beam.start + dir * ((i as f32 + 0.5) * len / segments as f32);
+            // Bright inner core
+            draw_sphere(seg_pos, 0.05, None, Color::new(beam.color.r, beam.color.g, beam.color.b, alpha));
+            // Translucent outer glow
+            draw_sphere(seg_pos, 0.12, None, Color::new(beam.color.r, beam.color.g, beam.color.b, alpha * 0.35));
+        }
     }
 
     // Pickups (bobbing and spinning!)
@@ -1571,23 +3171,29 @@ fn render_3d(world: &World) {
     for pickup in &world.pickups {
         if pickup.collected { continue; }
 
-        let bob = (time * 3.0 + pickup.bob_offset).sin() * 0.2;
-        let pos = vec3(pickup.pos.x, pickup.pos.y + bob + 0.5, pickup.pos.z);
+        // Still bouncing from a kill - tumble in place rather than bob.
+        let (pos, size) = if pickup.settled {
+            let bob = (time * 3.0 + pickup.bob_offset).sin() * 0.2;
+            (vec3(pickup.pos.x, pickup.pos.y + bob + 0.5, pickup.pos.z), vec3(0.5, 0.5, 0.5))
+        } else {
+            let tumble = pickup.spin.sin() * 0.15;
+            (pickup.pos, vec3(0.5 + tumble, 0.5 - tumble, 0.5 + tumble))
+        };
 
         // Draw pickup as glowing cube
         let color = pickup.color();
         let pulse = ((time * 5.0).sin() * 0.3 + 0.7) as f32;
         let glow_color = Color::new(color.r * pulse, color.g * pulse, color.b * pulse, 1.0);
 
-        draw_cube(pos, vec3(0.5, 0.5, 0.5), None, glow_color);
-        draw_cube_wires(pos, vec3(0.6, 0.6, 0.6), color);
+        draw_cube(pos, size, None, glow_color);
+        draw_cube_wires(pos, size + vec3(0.1, 0.1, 0.1), color);
 
         // Inner glow
         draw_sphere(pos, 0.3, None, Color::new(color.r, color.g, color.b, 0.5));
     }
 
     // Render first-person 3D weapon (before switching to 2D)
-    render_weapon_3d(world);
+    render_weapon_3d(world, idx);
 
     set_default_camera();
 }
@@ -1672,13 +3278,17 @@ fn draw_oriented_box(center: Vec3, half_extents: Vec3, right: Vec3, up: Vec3, fo
     draw_mesh(&mesh);
 }
 
-fn render_weapon_3d(world: &World) {
-    let time = get_time() as f32;
-    let aim = world.player.aim_transition;
+/// Computes the drawn weapon's world-space anchor (`weapon_pos`) and the
+/// player's `forward`/`right`/`up` basis it's built from, applying the same
+/// ADS-blend, idle sway, and muzzle kick that `render_weapon_3d` renders
+/// the gun model around. Shared so the barrel tip used for the 3D gun and
+/// the one used to spawn shots/flashes (`weapon_muzzle_tag`) never drift.
+fn weapon_sway_anchor(player: &Player, muzzle_flash: f32, time: f32) -> (Vec3, Vec3, Vec3, Vec3) {
+    let aim = player.aim_transition;
 
     // Get player orientation vectors
-    let forward = world.player.forward();
-    let right = world.player.right();
+    let forward = player.forward();
+    let right = player.right();
     let up = vec3(0.0, 1.0, 0.0);
 
     // Weapon sway (reduced when aiming)
@@ -1687,7 +3297,7 @@ fn render_weapon_3d(world: &World) {
     let sway_y = (time * 2.0).cos() * 0.01 * sway_amount;
 
     // Muzzle kick
-    let kick = world.muzzle_flash * 0.05;
+    let kick = muzzle_flash * 0.05;
 
     // Base position: in front of camera, offset right and down
     // When ADS: move to center
@@ -1703,12 +3313,48 @@ fn render_weapon_3d(world: &World) {
     let right_offset = base_right + (ads_right - base_right) * aim + sway_x;
     let down_offset = base_down + (ads_down - base_down) * aim + sway_y + kick;
 
-    let weapon_pos = world.player.pos
+    let weapon_pos = player.pos
         + forward * fwd_offset
         + right * right_offset
         - up * down_offset;
 
-    let weapon = &world.player.weapons[world.player.current_weapon];
+    (weapon_pos, forward, right, up)
+}
+
+/// How far out along the barrel, and how far laterally, each weapon's
+/// drawn muzzle sits relative to `weapon_pos` - matches the barrel boxes
+/// `render_weapon_3d` draws for that weapon.
+fn weapon_barrel_offset(wtype: WeaponType) -> (f32, f32) {
+    match wtype {
+        WeaponType::Pistol => (0.11, 0.0),
+        WeaponType::Shotgun => (0.25, 0.012),
+        WeaponType::MachineGun => (0.28, 0.0),
+        WeaponType::Rocket => (0.23, 0.0),
+        WeaponType::Railgun => (0.4, 0.0),
+    }
+}
+
+/// Projection source tag: the world-space tip of the currently-drawn
+/// barrel and the direction it points, reusing `render_weapon_3d`'s own
+/// sway/ADS/kick math so the muzzle flash and every spawned shot or
+/// projectile originate at the same point the gun model is rendered at.
+fn weapon_muzzle_tag(world: &World, idx: usize) -> (Vec3, Vec3) {
+    let player = world.player_ref(idx);
+    let time = get_time() as f32;
+    let (weapon_pos, forward, right, _up) = weapon_sway_anchor(player, world.muzzle_flash, time);
+    let weapon = &player.weapons[player.current_weapon];
+    let (barrel_len, barrel_lateral) = weapon_barrel_offset(weapon.wtype);
+    let origin = weapon_pos + forward * barrel_len + right * barrel_lateral;
+    (origin, forward)
+}
+
+fn render_weapon_3d(world: &World, idx: usize) {
+    let player = world.player_ref(idx);
+    let time = get_time() as f32;
+
+    let (weapon_pos, forward, right, up) = weapon_sway_anchor(player, world.muzzle_flash, time);
+
+    let weapon = &player.weapons[player.current_weapon];
 
     // Colors
     let metal_dark = Color::new(0.15, 0.15, 0.18, 1.0);
@@ -1793,28 +3439,43 @@ fn render_weapon_3d(world: &World) {
             let sight_pos = weapon_pos + up * 0.06 + forward * 0.05;
             draw_box(sight_pos, vec3(0.04, 0.03, 0.08), olive);
         }
+        WeaponType::Railgun => {
+            // Long thin receiver
+            let receiver_pos = weapon_pos - forward * 0.02;
+            draw_box(receiver_pos, vec3(0.03, 0.03, 0.2), metal_dark);
+            // Barrel
+            let barrel_pos = weapon_pos + forward * 0.25;
+            draw_box(barrel_pos, vec3(0.018, 0.018, 0.3), metal_light);
+            // Glowing coils along the barrel, pulsing hotter with charge
+            let charge_frac = (weapon.charge / weapon.charge_time).min(1.0);
+            let coil_glow = railgun_beam_color(charge_frac);
+            for i in 0..3 {
+                let coil_pos = weapon_pos + forward * (0.13 + i as f32 * 0.09);
+                draw_box(coil_pos, vec3(0.03, 0.03, 0.02), coil_glow);
+            }
+            // Grip
+            let grip_pos = weapon_pos - up * 0.06 - forward * 0.05;
+            draw_box(grip_pos, vec3(0.03, 0.06, 0.04), metal_dark);
+        }
     }
 
     // Muzzle flash (3D sphere at barrel end)
     if world.muzzle_flash > 0.3 {
-        let flash_dist = match weapon.wtype {
-            WeaponType::Pistol => 0.12,
-            WeaponType::Shotgun => 0.26,
-            WeaponType::MachineGun => 0.28,
-            WeaponType::Rocket => 0.24,
-        };
-        let flash_pos = weapon_pos + forward * flash_dist;
+        let (flash_pos, _) = weapon_muzzle_tag(world, idx);
         let flash_size = world.muzzle_flash * 0.08;
         draw_sphere(flash_pos, flash_size, None, Color::new(1.0, 0.9, 0.4, world.muzzle_flash));
         draw_sphere(flash_pos, flash_size * 0.5, None, Color::new(1.0, 1.0, 0.8, world.muzzle_flash));
     }
 }
 
-fn render_hud(world: &World) {
-    let sw = screen_width();
-    let sh = screen_height();
-    let cx = sw / 2.0;
-    let cy = sh / 2.0;
+/// Draws one player's HUD confined to `(ox, oy, vw, vh)` - the player's
+/// half of the screen in co-op, or the whole screen in solo play.
+fn render_hud(world: &World, idx: usize, (ox, oy, vw, vh): (f32, f32, f32, f32)) {
+    let player = world.player_ref(idx);
+    let sw = vw;
+    let sh = vh;
+    let cx = ox + sw / 2.0;
+    let cy = oy + sh / 2.0;
 
     // Crosshair
     let cross_color = if world.hit_marker > 0.0 { RED } else { WHITE };
@@ -1832,100 +3493,179 @@ fn render_hud(world: &World) {
         draw_line(cx + s, cy + s, cx + 4.0, cy + 4.0, 2.0, RED);
     }
 
+    // Charge meter: an arc around the crosshair that fills with
+    // charge / charge_time, flashing red once the hold crosses into the
+    // overcharge window instead of simply capping out.
+    let weapon = &player.weapons[player.current_weapon];
+    if weapon.chargeable && weapon.charge > 0.0 {
+        let charge_frac = (weapon.charge / weapon.charge_time).min(1.0);
+        let overcharging = weapon.charge > weapon.charge_time;
+        let flashing = overcharging && (get_time() * 12.0).sin() > 0.0;
+        let arc_color = if flashing { RED } else { cross_color };
+        let radius = 26.0;
+        let segments = 32;
+        let filled = ((segments as f32) * charge_frac).round() as i32;
+        for i in 0..filled {
+            let a0 = -PI / 2.0 + (i as f32 / segments as f32) * (2.0 * PI);
+            let a1 = -PI / 2.0 + ((i + 1) as f32 / segments as f32) * (2.0 * PI);
+            let p0 = vec2(cx + a0.cos() * radius, cy + a0.sin() * radius);
+            let p1 = vec2(cx + a1.cos() * radius, cy + a1.sin() * radius);
+            draw_line(p0.x, p0.y, p1.x, p1.y, 3.0, arc_color);
+        }
+    }
+
     // Muzzle flash
     if world.muzzle_flash > 0.0 {
         draw_rectangle(cx - 40.0, cy + 80.0, 80.0, 40.0, Color::new(1.0, 0.8, 0.4, world.muzzle_flash * 0.5));
     }
 
+    // Sector hazard tint (underwater blue-green / lava orange), drawn
+    // before the damage flash so the two compose instead of one hiding
+    // the other.
+    if player.env_tint > 0.0 {
+        let tint = match player.env_tint_effect {
+            SectorEffect::Water => Color::new(0.1, 0.4, 0.5, player.env_tint * 0.35),
+            SectorEffect::Hazard => Color::new(1.0, 0.4, 0.0, player.env_tint * 0.3),
+            SectorEffect::None => Color::new(0.0, 0.0, 0.0, 0.0),
+        };
+        draw_rectangle(ox, oy, sw, sh, tint);
+    }
+
     // Damage flash
-    if world.player.damage_flash > 0.0 {
-        draw_rectangle(0.0, 0.0, sw, sh, Color::new(1.0, 0.0, 0.0, world.player.damage_flash * 0.3));
+    if player.damage_flash > 0.0 {
+        draw_rectangle(ox, oy, sw, sh, Color::new(1.0, 0.0, 0.0, player.damage_flash * 0.3));
     }
 
     // Speed boost screen tint
-    if world.player.speed_boost > 0.0 {
-        draw_rectangle(0.0, 0.0, sw, sh, Color::new(0.0, 0.5, 1.0, 0.1));
+    if player.speed_boost > 0.0 {
+        draw_rectangle(ox, oy, sw, sh, Color::new(0.0, 0.5, 1.0, 0.1));
     }
 
     // Damage boost screen tint
-    if world.player.damage_boost > 0.0 {
-        draw_rectangle(0.0, 0.0, sw, sh, Color::new(1.0, 0.3, 0.0, 0.1));
+    if player.damage_boost > 0.0 {
+        draw_rectangle(ox, oy, sw, sh, Color::new(1.0, 0.3, 0.0, 0.1));
     }
 
     // Health bar
-    let hp_pct = world.player.health / MAX_HEALTH;
-    draw_rectangle(20.0, sh - 60.0, 200.0, 30.0, DARKGRAY);
-    draw_rectangle(22.0, sh - 58.0, 196.0 * hp_pct, 26.0, Color::new(1.0 - hp_pct, hp_pct, 0.2, 1.0));
-    draw_text(&format!("HEALTH: {:.0}", world.player.health), 25.0, sh - 40.0, 20.0, WHITE);
+    let hp_pct = player.health / MAX_HEALTH;
+    draw_rectangle(ox + 20.0, oy + sh - 60.0, 200.0, 30.0, DARKGRAY);
+    draw_rectangle(ox + 22.0, oy + sh - 58.0, 196.0 * hp_pct, 26.0, Color::new(1.0 - hp_pct, hp_pct, 0.2, 1.0));
+    draw_text(&format!("HEALTH: {:.0}", player.health), ox + 25.0, oy + sh - 40.0, 20.0, WHITE);
 
     // Armor bar
-    if world.player.armor > 0.0 {
-        let armor_pct = world.player.armor / 100.0;
-        draw_rectangle(20.0, sh - 95.0, 200.0, 25.0, DARKGRAY);
-        draw_rectangle(22.0, sh - 93.0, 196.0 * armor_pct, 21.0, BLUE);
-        draw_text(&format!("ARMOR: {:.0}", world.player.armor), 25.0, sh - 78.0, 16.0, WHITE);
+    if player.armor > 0.0 {
+        let armor_pct = player.armor / 100.0;
+        draw_rectangle(ox + 20.0, oy + sh - 95.0, 200.0, 25.0, DARKGRAY);
+        draw_rectangle(ox + 22.0, oy + sh - 93.0, 196.0 * armor_pct, 21.0, BLUE);
+        draw_text(&format!("ARMOR: {:.0}", player.armor), ox + 25.0, oy + sh - 78.0, 16.0, WHITE);
     }
 
     // Powerup indicators
-    let mut powerup_y = sh - 130.0;
-    if world.player.speed_boost > 0.0 {
-        draw_rectangle(20.0, powerup_y, 150.0, 20.0, Color::new(0.0, 0.0, 0.0, 0.6));
-        draw_text(&format!("SPEED: {:.1}s", world.player.speed_boost), 25.0, powerup_y + 15.0, 16.0, SKYBLUE);
+    let mut powerup_y = oy + sh - 130.0;
+    if player.speed_boost > 0.0 {
+        draw_rectangle(ox + 20.0, powerup_y, 150.0, 20.0, Color::new(0.0, 0.0, 0.0, 0.6));
+        draw_text(&format!("SPEED: {:.1}s", player.speed_boost), ox + 25.0, powerup_y + 15.0, 16.0, SKYBLUE);
         powerup_y -= 25.0;
     }
-    if world.player.damage_boost > 0.0 {
-        draw_rectangle(20.0, powerup_y, 150.0, 20.0, Color::new(0.0, 0.0, 0.0, 0.6));
-        draw_text(&format!("DAMAGE x2: {:.1}s", world.player.damage_boost), 25.0, powerup_y + 15.0, 16.0, ORANGE);
+    if player.damage_boost > 0.0 {
+        draw_rectangle(ox + 20.0, powerup_y, 150.0, 20.0, Color::new(0.0, 0.0, 0.0, 0.6));
+        draw_text(&format!("DAMAGE x2: {:.1}s", player.damage_boost), ox + 25.0, powerup_y + 15.0, 16.0, ORANGE);
     }
 
     // Weapon info
-    let weapon = &world.player.weapons[world.player.current_weapon];
-    draw_rectangle(sw - 220.0, sh - 100.0, 200.0, 80.0, Color::new(0.0, 0.0, 0.0, 0.6));
-    draw_text(weapon.name(), sw - 210.0, sh - 75.0, 24.0, YELLOW);
-    let ammo_str = if weapon.ammo < 0 { "INF".into() } else { format!("{}/{}", weapon.ammo, weapon.max_ammo) };
-    draw_text(&ammo_str, sw - 210.0, sh - 45.0, 28.0, WHITE);
+    let weapon = &player.weapons[player.current_weapon];
+    draw_rectangle(ox + sw - 220.0, oy + sh - 100.0, 200.0, 80.0, Color::new(0.0, 0.0, 0.0, 0.6));
+    draw_text(weapon.name(), ox + sw - 210.0, oy + sh - 75.0, 24.0, YELLOW);
+    let ammo_str = if weapon.reloading {
+        "RELOADING".to_string()
+    } else if weapon.reserve < 0 {
+        format!("{}", weapon.magazine)
+    } else {
+        format!("{}/{}", weapon.magazine, weapon.reserve)
+    };
+    draw_text(&ammo_str, ox + sw - 210.0, oy + sh - 45.0, 28.0, WHITE);
 
-    // Weapon selector
+    // Weapon selector - unowned weapons are greyed out
     for i in 0..4 {
-        let x = sw - 220.0 + i as f32 * 50.0;
-        let color = if i == world.player.current_weapon { YELLOW } else { GRAY };
-        draw_rectangle(x, sh - 130.0, 40.0, 25.0, Color::new(0.0, 0.0, 0.0, 0.6));
-        draw_text(&format!("{}", i + 1), x + 15.0, sh - 110.0, 18.0, color);
+        let x = ox + sw - 220.0 + i as f32 * 50.0;
+        let owned = player.weapons[i].owned;
+        let color = if !owned { DARKGRAY } else if i == player.current_weapon { YELLOW } else { GRAY };
+        draw_rectangle(x, oy + sh - 130.0, 40.0, 25.0, Color::new(0.0, 0.0, 0.0, 0.6));
+        draw_text(&format!("{}", i + 1), x + 15.0, oy + sh - 110.0, 18.0, color);
     }
 
     // Score, kills and level
-    draw_text(&format!("SCORE: {}", world.player.score), 20.0, 35.0, 28.0, YELLOW);
-    draw_text(&format!("KILLS: {}", world.player.kills), 200.0, 35.0, 20.0, RED);
-    draw_text(&format!("LEVEL {}/5: {}", world.current_level, world.level.name), 20.0, 60.0, 20.0, WHITE);
+    draw_text(&format!("SCORE: {}", player.score), ox + 20.0, oy + 35.0, 28.0, YELLOW);
+    draw_text(&format!("KILLS: {}", player.kills), ox + 200.0, oy + 35.0, 20.0, RED);
+    draw_text(&format!("LEVEL {}/{}: {}", world.current_level, world.playlist.len(), world.level.name), ox + 20.0, oy + 60.0, 20.0, WHITE);
 
     // Combo display
     if world.combo > 1 {
         let combo_scale = 1.0 + world.combo as f32 * 0.1;
-        draw_text(&format!("COMBO x{}!", world.combo), cx - 60.0, 140.0, (28.0 * combo_scale) as u16 as f32, ORANGE);
+        draw_text(&format!("COMBO x{}!", world.combo), cx - 60.0, oy + 140.0, (28.0 * combo_scale) as u16 as f32, ORANGE);
     }
 
     let alive = world.alive_enemies();
     let enemy_color = if alive > 0 { RED } else { GREEN };
-    draw_text(&format!("ENEMIES: {}", alive), 20.0, 85.0, 20.0, enemy_color);
+    draw_text(&format!("ENEMIES: {}", alive), ox + 20.0, oy + 85.0, 20.0, enemy_color);
 
     if alive == 0 {
-        draw_text("ALL CLARKSONS DEFEATED! FIND THE EXIT!", cx - 200.0, 100.0, 24.0, GREEN);
+        draw_text("ALL CLARKSONS DEFEATED! FIND THE EXIT!", cx - 200.0, oy + 100.0, 24.0, GREEN);
     }
 
     // Pickup message
-    if world.player.pickup_msg_time > 0.0 {
-        let alpha = world.player.pickup_msg_time.min(1.0);
-        draw_text(&world.player.pickup_msg, cx - 50.0, cy + 50.0, 24.0, Color::new(0.0, 1.0, 0.0, alpha));
+    if player.pickup_msg_time > 0.0 {
+        let alpha = player.pickup_msg_time.min(1.0);
+        draw_text(&player.pickup_msg, cx - 50.0, cy + 50.0, 24.0, Color::new(0.0, 1.0, 0.0, alpha));
+    }
+
+    // Floating damage numbers - projected from world space into this
+    // viewport with the same camera render_3d just drew with (minus its
+    // screen-shake jitter), so they always land over the hit that spawned
+    // them and, being flat 2D text, always face the player like a billboard.
+    let hud_cam = Camera3D {
+        position: player.pos,
+        target: player.pos + player.forward() * 10.0,
+        up: Vec3::Y,
+        fovy: world.settings.fov,
+        projection: Projection::Perspective,
+        ..Default::default()
+    };
+    let now = get_time();
+    for ft in &world.floating_texts {
+        let age = (now - ft.birth_time) as f32;
+        let life_frac = (age / FLOATING_TEXT_LIFETIME).clamp(0.0, 1.0);
+        let rise = age * 1.2;
+        let Some(screen_pos) = world_to_screen_3d(&hud_cam, (ox, oy, sw, sh), ft.world_pos + vec3(0.0, rise, 0.0)) else { continue };
+
+        // Far-off hits shrink toward unreadable, so clamp how far distance can scale them down.
+        let dist = (ft.world_pos - player.pos).length().max(4.0);
+        let base_size = if ft.crit { 34.0 } else { 20.0 };
+        let font_size = base_size * (12.0 / dist).clamp(0.4, 1.4);
+        let mut color = ft.color;
+        color.a = 1.0 - life_frac;
+
+        draw_text(&ft.text, screen_pos.x, screen_pos.y, font_size, color);
     }
 
     // Minimap
-    render_minimap(world);
+    render_minimap(world, idx, (ox, oy, vw));
 }
 
-fn render_minimap(world: &World) {
-    let sw = screen_width();
+/// Wall/exit/floor coloring shared by the corner `render_minimap` and the
+/// full-screen `render_automap` so both always agree on what a tile means.
+fn map_cell_color(c: char) -> Color {
+    match c {
+        '#' => Color::new(0.3, 0.3, 0.35, 1.0),
+        'X' => GREEN,
+        _ => Color::new(0.1, 0.1, 0.12, 1.0),
+    }
+}
+
+fn render_minimap(world: &World, idx: usize, (ox, _oy, vw): (f32, f32, f32)) {
+    let player = world.player_ref(idx);
     let cell = 5.0;
-    let map_x = sw - 160.0;
+    let map_x = ox + vw - 160.0;
     let map_y = 20.0;
     let max_cells = 25;
 
@@ -1937,26 +3677,21 @@ fn render_minimap(world: &World) {
     for y in 0..h {
         for x in 0..w {
             let c = world.level.grid[y][x];
-            let color = match c {
-                '#' => Color::new(0.3, 0.3, 0.35, 1.0),
-                'X' => GREEN,
-                _ => Color::new(0.1, 0.1, 0.12, 1.0),
-            };
-            draw_rectangle(map_x + x as f32 * cell, map_y + y as f32 * cell, cell - 1.0, cell - 1.0, color);
+            draw_rectangle(map_x + x as f32 * cell, map_y + y as f32 * cell, cell - 1.0, cell - 1.0, map_cell_color(c));
         }
     }
 
     // Player
-    let px = (world.player.pos.x / CELL_SIZE) * cell;
-    let pz = (world.player.pos.z / CELL_SIZE) * cell;
+    let px = (player.pos.x / CELL_SIZE) * cell;
+    let pz = (player.pos.z / CELL_SIZE) * cell;
     draw_rectangle(map_x + px - cell/2.0, map_y + pz - cell/2.0, cell, cell, BLUE);
 
     // Direction
     let dir_len = 8.0;
     draw_line(
         map_x + px, map_y + pz,
-        map_x + px + world.player.yaw.cos() * dir_len,
-        map_y + pz + world.player.yaw.sin() * dir_len,
+        map_x + px + player.yaw.cos() * dir_len,
+        map_y + pz + player.yaw.sin() * dir_len,
         2.0, SKYBLUE
     );
 
@@ -1969,7 +3704,81 @@ fn render_minimap(world: &World) {
     }
 }
 
-fn render_menu() {
+/// Full-screen automap, toggled by `world.automap_open` - unlike the
+/// always-on corner `render_minimap` this scales the whole `level.grid` to
+/// fit the window (not clamped to 25x25) and adds a per-level stats panel.
+/// Shares `map_cell_color` with the minimap so walls/exit/floor match.
+fn render_automap(world: &World, idx: usize) {
+    let player = world.player_ref(idx);
+    let sw = screen_width();
+    let sh = screen_height();
+
+    draw_rectangle(0.0, 0.0, sw, sh, Color::new(0.0, 0.0, 0.0, 0.85));
+
+    let panel_w = 260.0;
+    let map_area_w = sw - panel_w - 30.0;
+    let map_area_h = sh - 40.0;
+    let cell = (map_area_w / world.level.width as f32).min(map_area_h / world.level.height as f32);
+    let map_x = 20.0;
+    let map_y = 20.0;
+
+    for y in 0..world.level.height {
+        for x in 0..world.level.width {
+            let c = world.level.grid[y][x];
+            draw_rectangle(map_x + x as f32 * cell, map_y + y as f32 * cell, cell - 1.0, cell - 1.0, map_cell_color(c));
+        }
+    }
+
+    // Pickups
+    for pickup in &world.pickups {
+        if pickup.collected { continue; }
+        let px = (pickup.pos.x / CELL_SIZE) * cell;
+        let pz = (pickup.pos.z / CELL_SIZE) * cell;
+        draw_circle(map_x + px, map_y + pz, cell * 0.3, pickup.color());
+    }
+
+    // Enemies
+    for enemy in &world.enemies {
+        if enemy.dead { continue; }
+        let ex = (enemy.pos.x / CELL_SIZE) * cell;
+        let ez = (enemy.pos.z / CELL_SIZE) * cell;
+        draw_rectangle(map_x + ex - cell * 0.4, map_y + ez - cell * 0.4, cell * 0.8, cell * 0.8, RED);
+    }
+
+    // Player heading
+    let px = (player.pos.x / CELL_SIZE) * cell;
+    let pz = (player.pos.z / CELL_SIZE) * cell;
+    draw_rectangle(map_x + px - cell * 0.4, map_y + pz - cell * 0.4, cell * 0.8, cell * 0.8, BLUE);
+    let dir_len = cell * 2.0;
+    draw_line(
+        map_x + px, map_y + pz,
+        map_x + px + player.yaw.cos() * dir_len,
+        map_y + pz + player.yaw.sin() * dir_len,
+        3.0, SKYBLUE,
+    );
+
+    // Stats panel
+    let panel_x = sw - panel_w;
+    draw_rectangle(panel_x, 20.0, panel_w - 20.0, sh - 40.0, Color::new(0.05, 0.05, 0.08, 0.9));
+    let kills_this_level: i32 = world.player_indices().map(|i| world.player_ref(i).kills).sum();
+    let alive = world.alive_enemies();
+    let lines = [
+        world.level.name.clone(),
+        format!("Level {}/{}", world.current_level, world.playlist.len()),
+        String::new(),
+        format!("Kills this level: {}", kills_this_level),
+        format!("Enemies remaining: {}", alive),
+        format!("Score: {}", player.score),
+        format!("Combo: x{}", world.combo),
+    ];
+    for (i, line) in lines.iter().enumerate() {
+        draw_text(line, panel_x + 15.0, 60.0 + i as f32 * 28.0, 20.0, WHITE);
+    }
+
+    draw_text("TAB / Gamepad Select - Close", panel_x + 15.0, sh - 30.0, 16.0, GRAY);
+}
+
+fn render_menu(coop_requested: bool) {
     let sw = screen_width();
     let sh = screen_height();
     let time = get_time() as f32;
@@ -1995,14 +3804,21 @@ fn render_menu() {
         "Left Click - Shoot",
         "1-4 / Scroll - Weapons",
         "Shift - Sprint",
+        "Tab - Automap",
         "ESC - Pause"
     ];
     for (i, c) in controls.iter().enumerate() {
         draw_text(c, sw/2.0 - 90.0, sh/2.0 + 60.0 + i as f32 * 25.0, 18.0, LIGHTGRAY);
     }
 
+    // Co-op toggle
+    let coop_label = if coop_requested { "C - Co-op: ON (player 2 needs a gamepad)" } else { "C - Co-op: OFF" };
+    let coop_color = if coop_requested { GREEN } else { LIGHTGRAY };
+    draw_text(coop_label, sw/2.0 - 90.0, sh/2.0 + 60.0 + controls.len() as f32 * 25.0, 18.0, coop_color);
+
     // Features list
     draw_text("5 LEVELS - POWERUPS - COMBOS - ARMOR", sw/2.0 - 180.0, sh - 80.0, 18.0, SKYBLUE);
+    draw_text("O / Gamepad RB - Options", sw/2.0 - 95.0, sh - 60.0, 16.0, LIGHTGRAY);
     draw_text("Built with Rust + macroquad", sw/2.0 - 110.0, sh - 40.0, 16.0, DARKGRAY);
 }
 
@@ -2013,6 +3829,53 @@ fn render_pause() {
     draw_rectangle(0.0, 0.0, sw, sh, Color::new(0.0, 0.0, 0.0, 0.6));
     draw_text("PAUSED", sw/2.0 - 70.0, sh/2.0 - 20.0, 48.0, WHITE);
     draw_text("Press ESC to Resume", sw/2.0 - 100.0, sh/2.0 + 30.0, 20.0, GRAY);
+    draw_text("O / Gamepad RB - Options", sw/2.0 - 95.0, sh/2.0 + 55.0, 16.0, GRAY);
+}
+
+/// The `GameState::Options` screen - `OPTIONS_ROW_COUNT` adjustable rows,
+/// navigable with DPad/stick up-down and adjusted with left/right, drawn
+/// over whatever screen it was opened from (`render_menu` or the frozen 3D
+/// view).
+fn render_options(world: &World) {
+    let sw = screen_width();
+    let sh = screen_height();
+
+    draw_rectangle(0.0, 0.0, sw, sh, Color::new(0.0, 0.0, 0.0, 0.7));
+    draw_text("OPTIONS", sw / 2.0 - 80.0, sh / 4.0, 44.0, WHITE);
+
+    let rows: [(&str, String); OPTIONS_ROW_COUNT] = [
+        ("Master Volume", format!("{:>3.0}%", world.settings.master_volume * 100.0)),
+        ("Mouse Sensitivity", format!("{:.1}", world.settings.mouse_sensitivity)),
+        ("Stick Sensitivity", format!("{:.1}", world.settings.stick_sensitivity)),
+        ("Invert Y", (if world.settings.invert_y { "ON" } else { "OFF" }).to_string()),
+        ("Field of View", format!("{:.0}", world.settings.fov)),
+        ("Stereo 3D", (if world.stereo_enabled { "ON" } else { "OFF" }).to_string()),
+        ("Stick Dead Zone", format!("{:>3.0}%", world.input.stick_deadzone * 100.0)),
+        ("Stick Response Curve", format!("{:.1}", world.input.stick_curve)),
+        ("Trigger Press", format!("{:.2}", world.input.trigger_press)),
+        ("Trigger Release", format!("{:.2}", world.input.trigger_release)),
+        ("Stereo Mirror HUD", (if world.stereo_mirror_hud { "ON" } else { "OFF" }).to_string()),
+    ];
+
+    let start_y = sh / 4.0 + 60.0;
+    for (i, (label, value)) in rows.iter().enumerate() {
+        let y = start_y + i as f32 * 36.0;
+        let selected = i == world.options_cursor;
+        let color = if selected { YELLOW } else { LIGHTGRAY };
+        if selected {
+            draw_text(">", sw / 2.0 - 220.0, y, 24.0, YELLOW);
+        }
+        draw_text(label, sw / 2.0 - 190.0, y, 22.0, color);
+        draw_text(value, sw / 2.0 + 120.0, y, 22.0, color);
+    }
+
+    draw_text(
+        "Up/Down - Select    Left/Right - Adjust    ESC - Back",
+        sw / 2.0 - 220.0,
+        start_y + OPTIONS_ROW_COUNT as f32 * 36.0 + 30.0,
+        18.0,
+        GRAY,
+    );
 }
 
 fn render_death(world: &World) {
@@ -2021,7 +3884,15 @@ fn render_death(world: &World) {
 
     draw_rectangle(0.0, 0.0, sw, sh, Color::new(0.2, 0.0, 0.0, 0.8));
     draw_text("YOU DIED", sw/2.0 - 100.0, sh/2.0 - 40.0, 56.0, RED);
-    draw_text(&format!("Final Score: {}", world.player.score), sw/2.0 - 90.0, sh/2.0 + 20.0, 24.0, WHITE);
+    if world.player2.is_some() {
+        let combined: i32 = world.player_indices().map(|i| world.player_ref(i).score).sum();
+        draw_text(&format!("Team Score: {}", combined), sw/2.0 - 90.0, sh/2.0 + 20.0, 24.0, WHITE);
+        for i in world.player_indices() {
+            draw_text(&format!("P{}: {}", i + 1, world.player_ref(i).score), sw/2.0 - 60.0, sh/2.0 + 20.0 + (i as f32 + 1.0) * 25.0, 18.0, LIGHTGRAY);
+        }
+    } else {
+        draw_text(&format!("Final Score: {}", world.player.score), sw/2.0 - 90.0, sh/2.0 + 20.0, 24.0, WHITE);
+    }
     draw_text("Press ENTER to Restart", sw/2.0 - 120.0, sh/2.0 + 60.0, 20.0, GRAY);
 }
 
@@ -2031,22 +3902,90 @@ fn render_victory(world: &World) {
 
     draw_rectangle(0.0, 0.0, sw, sh, Color::new(0.0, 0.2, 0.0, 0.8));
 
-    if world.current_level >= 5 {
+    let combined_score: i32 = world.player_indices().map(|i| world.player_ref(i).score).sum();
+    let combined_kills: i32 = world.player_indices().map(|i| world.player_ref(i).kills).sum();
+
+    if world.current_level >= world.playlist.len() {
         draw_text("VICTORY!", sw/2.0 - 90.0, sh/2.0 - 100.0, 56.0, GREEN);
         draw_text("You defeated all the Clarksons!", sw/2.0 - 160.0, sh/2.0 - 40.0, 22.0, WHITE);
-        draw_text(&format!("Final Score: {}", world.player.score), sw/2.0 - 80.0, sh/2.0, 24.0, YELLOW);
+        draw_text(&format!("Final Score: {}", combined_score), sw/2.0 - 80.0, sh/2.0, 24.0, YELLOW);
         draw_text(&format!("Total Kills: {}", world.total_kills), sw/2.0 - 70.0, sh/2.0 + 35.0, 20.0, RED);
         draw_text(&format!("Best Combo: x{}", world.combo), sw/2.0 - 60.0, sh/2.0 + 60.0, 20.0, ORANGE);
         draw_text("Press ENTER for Menu", sw/2.0 - 100.0, sh/2.0 + 100.0, 20.0, GRAY);
     } else {
         draw_text("LEVEL COMPLETE!", sw/2.0 - 140.0, sh/2.0 - 60.0, 48.0, GREEN);
-        draw_text(&format!("Score: {}", world.player.score), sw/2.0 - 50.0, sh/2.0, 24.0, WHITE);
-        draw_text(&format!("Kills this level: {}", world.player.kills), sw/2.0 - 80.0, sh/2.0 + 30.0, 18.0, RED);
+        draw_text(&format!("Score: {}", combined_score), sw/2.0 - 50.0, sh/2.0, 24.0, WHITE);
+        draw_text(&format!("Kills this level: {}", combined_kills), sw/2.0 - 80.0, sh/2.0 + 30.0, 18.0, RED);
         draw_text(&format!("Next: Level {}/5", world.current_level + 1), sw/2.0 - 60.0, sh/2.0 + 60.0, 18.0, SKYBLUE);
         draw_text("Press ENTER for Next Level", sw/2.0 - 130.0, sh/2.0 + 95.0, 20.0, GRAY);
     }
 }
 
+// ============================================================================
+// CHEATS
+// ============================================================================
+
+/// Max characters kept in `World::cheat_buffer` - old typing falls off the
+/// front so the buffer never grows unbounded.
+const CHEAT_BUFFER_LEN: usize = 30;
+
+/// Code -> effect, checked as a *suffix* of the rolling buffer each frame so
+/// overlapping/partial typing still resolves once the full code lands.
+const CHEATS: &[(&str, fn(&mut World))] = &[
+    ("IDKFA", cheat_refill_arsenal),
+    ("GODMODE", cheat_toggle_godmode),
+    ("WARP5", cheat_warp5),
+    ("BIGSCORE", cheat_bigscore),
+];
+
+/// Grants every weapon and tops magazine/reserve back up to max.
+fn cheat_refill_arsenal(world: &mut World) {
+    let p = world.player_mut(0);
+    for weapon in &mut p.weapons {
+        weapon.owned = true;
+        weapon.magazine = weapon.max_magazine;
+        if weapon.max_reserve >= 0 {
+            weapon.reserve = weapon.max_reserve;
+        }
+    }
+}
+
+fn cheat_toggle_godmode(world: &mut World) {
+    let p = world.player_mut(0);
+    p.invulnerable = !p.invulnerable;
+}
+
+fn cheat_warp5(world: &mut World) {
+    world.load_level(5);
+}
+
+fn cheat_bigscore(world: &mut World) {
+    world.player_mut(0).score += 10000;
+}
+
+/// Appends a newly typed char to the rolling cheat buffer, checks it
+/// against `CHEATS`, and fires the matching effect on a hit - flashing a
+/// confirmation through the same `pickup_msg` HUD path a real pickup uses.
+fn feed_cheat_char(world: &mut World, c: char) {
+    if !c.is_ascii_alphanumeric() { return; }
+    world.cheat_buffer.push(c.to_ascii_uppercase());
+    let overflow = world.cheat_buffer.len().saturating_sub(CHEAT_BUFFER_LEN);
+    if overflow > 0 {
+        world.cheat_buffer.drain(0..overflow);
+    }
+
+    for (code, effect) in CHEATS {
+        if world.cheat_buffer.ends_with(code) {
+            effect(world);
+            world.cheat_buffer.clear();
+            let p = world.player_mut(0);
+            p.pickup_msg = format!("CHEAT: {}", code);
+            p.pickup_msg_time = 2.0;
+            break;
+        }
+    }
+}
+
 // ============================================================================
 // MAIN
 // ============================================================================
@@ -2069,6 +4008,16 @@ fn window_conf() -> Conf {
 async fn main() {
     let mut world = World::new();
     let mut gamepad = GamepadState::default();
+    let mut gamepad2 = GamepadState::default();
+    // Pending mouse/key input not yet consumed by a fixed tick - persists
+    // across real frames instead of being recaptured fresh each frame, so
+    // nothing is lost on a frame where no tick runs.
+    let mut frame_input = FrameInput::default();
+
+    // The first gamepad seen drives player one, the second drives player
+    // two in co-op. Ids are assigned by gilrs as controllers connect.
+    let mut gamepad_id: Option<gilrs::GamepadId> = None;
+    let mut gamepad2_id: Option<gilrs::GamepadId> = None;
 
     // Initialize gilrs for gamepad support
     let mut gilrs = Gilrs::new().unwrap_or_else(|e| {
@@ -2092,87 +4041,169 @@ async fn main() {
     loop {
         let dt = get_frame_time();
 
-        // Poll gamepad events
-        gamepad.clear_just_pressed();
-        while let Some(Event { id: _, event, time: _ }) = gilrs.next_event() {
+        // Cheat codes - fed from the same typed keys as everything else,
+        // independent of game state so they work from the menu too.
+        while let Some(c) = get_char_pressed() {
+            feed_cheat_char(&mut world, c);
+        }
+
+        // Poll gamepad events. `*_just_pressed`/`nav_*_just` aren't cleared
+        // here - only once a fixed tick actually consumes them below - so a
+        // button press isn't lost on a real frame that advances no tick.
+        while let Some(Event { id, event, time: _ }) = gilrs.next_event() {
+            let pad = if gamepad_id == Some(id) {
+                &mut gamepad
+            } else if gamepad2_id == Some(id) {
+                &mut gamepad2
+            } else if gamepad_id.is_none() {
+                gamepad_id = Some(id);
+                &mut gamepad
+            } else if gamepad2_id.is_none() {
+                gamepad2_id = Some(id);
+                &mut gamepad2
+            } else {
+                continue; // only the first two controllers are wired up
+            };
+
             match event {
                 gilrs::EventType::ButtonPressed(button, _) => {
                     match button {
-                        Button::South => { gamepad.a_pressed = true; gamepad.a_just_pressed = true; }
-                        Button::East => { gamepad.b_pressed = true; gamepad.b_just_pressed = true; }
-                        Button::West => { gamepad.x_pressed = true; }
-                        Button::North => { gamepad.y_pressed = true; }
-                        Button::LeftTrigger => { gamepad.lb_pressed = true; gamepad.lb_just_pressed = true; }
-                        Button::RightTrigger => { gamepad.rb_pressed = true; gamepad.rb_just_pressed = true; }
-                        Button::LeftTrigger2 => { gamepad.lt_button = true; }  // Analog trigger as button
-                        Button::RightTrigger2 => { gamepad.rt_button = true; } // Analog trigger as button
-                        Button::Start => { gamepad.start_pressed = true; gamepad.start_just_pressed = true; }
-                        Button::DPadUp => { gamepad.dpad_up = true; gamepad.dpad_up_just = true; }
-                        Button::DPadDown => { gamepad.dpad_down = true; gamepad.dpad_down_just = true; }
-                        Button::DPadLeft => { gamepad.dpad_left = true; gamepad.dpad_left_just = true; }
-                        Button::DPadRight => { gamepad.dpad_right = true; gamepad.dpad_right_just = true; }
-                        Button::LeftThumb => { gamepad.left_thumb = true; }
+                        Button::South => { pad.a_pressed = true; pad.a_just_pressed = true; }
+                        Button::East => { pad.b_pressed = true; pad.b_just_pressed = true; }
+                        Button::West => { pad.x_pressed = true; }
+                        Button::North => { pad.y_pressed = true; }
+                        Button::LeftTrigger => { pad.lb_pressed = true; pad.lb_just_pressed = true; }
+                        Button::RightTrigger => { pad.rb_pressed = true; pad.rb_just_pressed = true; }
+                        Button::LeftTrigger2 => { pad.lt_button = true; }  // Analog trigger as button
+                        Button::RightTrigger2 => { pad.rt_button = true; } // Analog trigger as button
+                        Button::Start => { pad.start_pressed = true; pad.start_just_pressed = true; }
+                        Button::Select => { pad.select_pressed = true; pad.select_just_pressed = true; }
+                        Button::DPadUp => { pad.dpad_up = true; pad.dpad_up_just = true; }
+                        Button::DPadDown => { pad.dpad_down = true; pad.dpad_down_just = true; }
+                        Button::DPadLeft => { pad.dpad_left = true; pad.dpad_left_just = true; }
+                        Button::DPadRight => { pad.dpad_right = true; pad.dpad_right_just = true; }
+                        Button::LeftThumb => { pad.left_thumb = true; }
                         _ => {}
                     }
                 }
                 gilrs::EventType::ButtonReleased(button, _) => {
                     match button {
-                        Button::South => { gamepad.a_pressed = false; }
-                        Button::East => { gamepad.b_pressed = false; }
-                        Button::West => { gamepad.x_pressed = false; }
-                        Button::North => { gamepad.y_pressed = false; }
-                        Button::LeftTrigger => { gamepad.lb_pressed = false; }
-                        Button::RightTrigger => { gamepad.rb_pressed = false; }
-                        Button::LeftTrigger2 => { gamepad.lt_button = false; }
-                        Button::RightTrigger2 => { gamepad.rt_button = false; }
-                        Button::Start => { gamepad.start_pressed = false; }
-                        Button::DPadUp => { gamepad.dpad_up = false; }
-                        Button::DPadDown => { gamepad.dpad_down = false; }
-                        Button::DPadLeft => { gamepad.dpad_left = false; }
-                        Button::DPadRight => { gamepad.dpad_right = false; }
-                        Button::LeftThumb => { gamepad.left_thumb = false; }
+                        Button::South => { pad.a_pressed = false; }
+                        Button::East => { pad.b_pressed = false; }
+                        Button::West => { pad.x_pressed = false; }
+                        Button::North => { pad.y_pressed = false; }
+                        Button::LeftTrigger => { pad.lb_pressed = false; }
+                        Button::RightTrigger => { pad.rb_pressed = false; }
+                        Button::LeftTrigger2 => { pad.lt_button = false; }
+                        Button::RightTrigger2 => { pad.rt_button = false; }
+                        Button::Start => { pad.start_pressed = false; }
+                        Button::Select => { pad.select_pressed = false; }
+                        Button::DPadUp => { pad.dpad_up = false; }
+                        Button::DPadDown => { pad.dpad_down = false; }
+                        Button::DPadLeft => { pad.dpad_left = false; }
+                        Button::DPadRight => { pad.dpad_right = false; }
+                        Button::LeftThumb => { pad.left_thumb = false; }
                         _ => {}
                     }
                 }
                 gilrs::EventType::AxisChanged(axis, value, _) => {
                     match axis {
-                        Axis::LeftStickX => gamepad.left_stick_x = value,
-                        Axis::LeftStickY => gamepad.left_stick_y = value,
-                        Axis::RightStickX => gamepad.right_stick_x = value,
-                        Axis::RightStickY => gamepad.right_stick_y = value,
-                        Axis::LeftZ => gamepad.left_trigger = value,  // LT
-                        Axis::RightZ => gamepad.right_trigger = value, // RT
+                        Axis::LeftStickX => pad.left_stick_x = value,
+                        Axis::LeftStickY => pad.left_stick_y = value,
+                        Axis::RightStickX => pad.right_stick_x = value,
+                        Axis::RightStickY => pad.right_stick_y = value,
+                        Axis::LeftZ => pad.left_trigger = value,  // LT
+                        Axis::RightZ => pad.right_trigger = value, // RT
                         _ => {}
                     }
                 }
                 _ => {}
             }
         }
+        gamepad.update_nav();
+        gamepad2.update_nav();
+
+        // Fold this real frame's mouse motion and edge-triggered keys into
+        // any already-pending input, since macroquad only refreshes them
+        // once per rendered frame and the fixed-timestep loop below may run
+        // zero, one, or several ticks against it.
+        frame_input.accumulate();
+
+        // Step the simulation in fixed-size ticks regardless of frame rate,
+        // capping the backlog so a stall doesn't spiral into a burst of
+        // catch-up steps. Whatever real time is left over becomes the
+        // interpolation alpha render_3d blends prev_pos/pos with.
+        world.accumulator = (world.accumulator + dt).min(FIXED_DT * 5.0);
+        while world.accumulator >= FIXED_DT {
+            update(&mut world, FIXED_DT, &gamepad, &gamepad2, &frame_input);
+            world.accumulator -= FIXED_DT;
+
+            // Only the tick that actually consumes this pending input
+            // should see it - any further tick in the same frame (after a
+            // hitch) gets none, instead of replaying it.
+            frame_input.consume();
+            gamepad.clear_just_pressed();
+            gamepad2.clear_just_pressed();
+        }
 
-        update(&mut world, dt, &gamepad);
+        let sw = screen_width();
+        let sh = screen_height();
 
         match world.state {
             GameState::Menu => {
-                render_menu();
+                render_menu(world.coop_requested);
             }
             GameState::Playing | GameState::Paused => {
                 clear_background(Color::new(0.08, 0.08, 0.12, 1.0));
-                render_3d(&world);
-                render_hud(&world);
+                if world.player2.is_some() {
+                    render_3d(&world, 0, Some((0, 0, sw as i32 / 2, sh as i32)), Vec3::ZERO);
+                    render_3d(&world, 1, Some((sw as i32 / 2, 0, sw as i32 / 2, sh as i32)), Vec3::ZERO);
+                    render_hud(&world, 0, (0.0, 0.0, sw / 2.0, sh));
+                    render_hud(&world, 1, (sw / 2.0, 0.0, sw / 2.0, sh));
+                    draw_line(sw / 2.0, 0.0, sw / 2.0, sh, 2.0, WHITE);
+                } else if world.stereo_enabled {
+                    // Side-by-side stereo: the same single player's view,
+                    // rendered twice from cameras offset ±eye_separation/2
+                    // along their right vector, one per half of the window.
+                    let half_sep = world.player_ref(0).right() * (world.eye_separation * 0.5);
+                    render_3d(&world, 0, Some((0, 0, sw as i32 / 2, sh as i32)), -half_sep);
+                    render_3d(&world, 0, Some((sw as i32 / 2, 0, sw as i32 / 2, sh as i32)), half_sep);
+                    if world.stereo_mirror_hud {
+                        render_hud(&world, 0, (0.0, 0.0, sw / 2.0, sh));
+                        render_hud(&world, 0, (sw / 2.0, 0.0, sw / 2.0, sh));
+                    } else {
+                        render_hud(&world, 0, (0.0, 0.0, sw, sh));
+                    }
+                } else {
+                    render_3d(&world, 0, None, Vec3::ZERO);
+                    render_hud(&world, 0, (0.0, 0.0, sw, sh));
+                }
                 if world.state == GameState::Paused {
                     render_pause();
                 }
+                if world.automap_open {
+                    render_automap(&world, 0);
+                }
             }
             GameState::Dead => {
                 clear_background(Color::new(0.08, 0.08, 0.12, 1.0));
-                render_3d(&world);
+                render_3d(&world, 0, None, Vec3::ZERO);
                 render_death(&world);
             }
             GameState::Victory => {
                 clear_background(Color::new(0.08, 0.08, 0.12, 1.0));
-                render_3d(&world);
+                render_3d(&world, 0, None, Vec3::ZERO);
                 render_victory(&world);
             }
+            GameState::Options => {
+                if world.options_return_state == GameState::Menu {
+                    render_menu(world.coop_requested);
+                } else {
+                    clear_background(Color::new(0.08, 0.08, 0.12, 1.0));
+                    render_3d(&world, 0, None, Vec3::ZERO);
+                }
+                render_options(&world);
+            }
         }
 
         draw_text(&format!("FPS: {}", get_fps()), screen_width() - 80.0, 180.0, 16.0, WHITE);